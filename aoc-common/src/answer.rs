@@ -0,0 +1,61 @@
+use std::fmt::{self, Display};
+
+/// The answer of one part
+///
+/// The days return their own answer types, but the binaries only need to print or compare them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Unsigned(n) => n.fmt(f),
+            Answer::Signed(n) => n.fmt(f),
+            Answer::Text(s) => s.fmt(f),
+        }
+    }
+}
+
+macro_rules! impl_from {
+    ($variant:ident, $inner:ty, $($from:ty),+) => {
+        $(
+            impl From<$from> for Answer {
+                fn from(value: $from) -> Self {
+                    Answer::$variant(value as $inner)
+                }
+            }
+        )+
+    };
+}
+
+// usize always fits into u64 on the platforms we care about
+impl_from!(Unsigned, u64, u8, u16, u32, u64, usize);
+impl_from!(Signed, i64, i8, i16, i32, i64);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn displays_like_the_inner_value() {
+        assert_eq!(Answer::from(42_usize).to_string(), "42");
+        assert_eq!(Answer::from(-7_i32).to_string(), "-7");
+        assert_eq!(Answer::from("a,b").to_string(), "a,b");
+    }
+}
@@ -0,0 +1,426 @@
+//! Life-like cellular automata over any topology
+//!
+//! The [`Automaton`] keeps the set of the live cells. Each generation it counts the live neighbors of the cells
+//! with the [`Neighborhood`] into the [`Counts`] of the topology and asks the [`Rule`] which cells live on, so
+//! a new variant is just a new rule or a new neighborhood.
+//!
+//! The sets of the cells use the hash of rustc, which is much faster than SipHash for the small keys like the
+//! coordinates. The keys come from the puzzle, not from an attacker, so we don't need the protection of SipHash.
+
+use crate::grid::{Grid, Pos, ALL_AROUND};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::hash::Hash;
+
+/// The space where the cells live
+pub trait Topology {
+    type Cell: Copy + Eq + Hash;
+    type Counts: Counts<Self::Cell>;
+
+    /// Every cell, if there are finitely many of them
+    ///
+    /// Only these cells can be born without live neighbors.
+    fn cells(&self) -> Option<Vec<Self::Cell>>;
+
+    /// Empty counts for the neighbors of `live` live cells
+    fn counts(&self, live: usize) -> Self::Counts;
+}
+
+/// The numbers of live neighbors of the cells during a step
+pub trait Counts<C> {
+    fn add(&mut self, cell: C);
+
+    /// 0 for the cells that were never added
+    fn get(&self, cell: C) -> usize;
+
+    /// Calls `f` with each added cell and its count, once per cell
+    fn for_each(&self, f: impl FnMut(C, usize));
+}
+
+/// The counts of an infinite topology, only for the cells next to the live ones
+pub struct SparseCounts<C> {
+    counts: FxHashMap<C, usize>,
+}
+
+impl<C: Copy + Eq + Hash> SparseCounts<C> {
+    fn new(live: usize) -> Self {
+        // Most of the counted cells are next to several live ones
+        SparseCounts {
+            counts: FxHashMap::with_capacity_and_hasher(live * 2, Default::default()),
+        }
+    }
+}
+
+impl<C: Copy + Eq + Hash> Counts<C> for SparseCounts<C> {
+    fn add(&mut self, cell: C) {
+        *self.counts.entry(cell).or_default() += 1;
+    }
+
+    fn get(&self, cell: C) -> usize {
+        self.counts.get(&cell).copied().unwrap_or(0)
+    }
+
+    fn for_each(&self, mut f: impl FnMut(C, usize)) {
+        self.counts
+            .iter()
+            .for_each(|(&cell, &count)| f(cell, count));
+    }
+}
+
+/// The counts of a [`SquareGrid`], one per position, so counting is just indexing
+///
+/// A count stops at 255, which is more than any neighborhood of a grid sees.
+pub struct DenseCounts {
+    counts: Grid<u8>,
+    /// The positions with a count, in the order they were first added
+    counted: Vec<Pos>,
+}
+
+impl Counts<Pos> for DenseCounts {
+    fn add(&mut self, cell: Pos) {
+        // We may .unwrap() here, because the neighbors of a grid are always inside it
+        let count = self.counts.get_mut(cell).unwrap();
+        if *count == 0 {
+            self.counted.push(cell);
+        }
+        *count = count.saturating_add(1);
+    }
+
+    fn get(&self, cell: Pos) -> usize {
+        self.counts.get(cell).map_or(0, |&count| usize::from(count))
+    }
+
+    fn for_each(&self, mut f: impl FnMut(Pos, usize)) {
+        self.counted
+            .iter()
+            .for_each(|&cell| f(cell, self.get(cell)));
+    }
+}
+
+/// The cells that each cell looks at
+///
+/// The cells must look at each other both ways, because the automaton counts the neighbors from the live cells.
+pub trait Neighborhood<T: Topology> {
+    fn for_each_neighbor(&self, topology: &T, cell: T::Cell, f: impl FnMut(T::Cell));
+}
+
+/// A bounded square grid with holes that are never alive, like the floor between the seats
+pub struct SquareGrid {
+    is_cell: Grid<bool>,
+}
+
+impl SquareGrid {
+    pub fn new(is_cell: Grid<bool>) -> Self {
+        SquareGrid { is_cell }
+    }
+
+    fn is_cell(&self, pos: Pos) -> bool {
+        self.is_cell.get(pos) == Some(&true)
+    }
+}
+
+impl Topology for SquareGrid {
+    type Cell = Pos;
+    type Counts = DenseCounts;
+
+    fn cells(&self) -> Option<Vec<Pos>> {
+        Some(
+            self.is_cell
+                .positions()
+                .filter(|&p| self.is_cell(p))
+                .collect(),
+        )
+    }
+
+    fn counts(&self, live: usize) -> DenseCounts {
+        DenseCounts {
+            counts: Grid::new(self.is_cell.width(), self.is_cell.height(), 0),
+            counted: Vec::with_capacity(live * 2),
+        }
+    }
+}
+
+/// The infinite lattice of `DIM` dimensions
+pub struct Lattice<const DIM: usize>;
+
+impl<const DIM: usize> Topology for Lattice<DIM> {
+    type Cell = [i32; DIM];
+    type Counts = SparseCounts<Self::Cell>;
+
+    fn cells(&self) -> Option<Vec<Self::Cell>> {
+        None
+    }
+
+    fn counts(&self, live: usize) -> Self::Counts {
+        SparseCounts::new(live)
+    }
+}
+
+/// The infinite grid of hexagons in axial coordinates
+///
+/// https://www.redblobgames.com/grids/hexagons/
+pub struct HexGrid;
+
+impl Topology for HexGrid {
+    type Cell = (i32, i32);
+    type Counts = SparseCounts<Self::Cell>;
+
+    fn cells(&self) -> Option<Vec<Self::Cell>> {
+        None
+    }
+
+    fn counts(&self, live: usize) -> Self::Counts {
+        SparseCounts::new(live)
+    }
+}
+
+/// All the cells that touch the cell, even by a corner
+pub struct Moore;
+
+impl Neighborhood<SquareGrid> for Moore {
+    fn for_each_neighbor(&self, topology: &SquareGrid, cell: Pos, f: impl FnMut(Pos)) {
+        topology
+            .is_cell
+            .neighbors8(cell)
+            .filter(|&p| topology.is_cell(p))
+            .for_each(f);
+    }
+}
+
+impl<const DIM: usize> Neighborhood<Lattice<DIM>> for Moore {
+    fn for_each_neighbor(&self, _: &Lattice<DIM>, cell: [i32; DIM], mut f: impl FnMut([i32; DIM])) {
+        // Each offset is a number in base 3 with the digits -1, 0 and 1
+        for offset in 0..3_usize.pow(DIM as u32) {
+            let mut neighbor = cell;
+            let mut rest = offset;
+            for coord in neighbor.iter_mut() {
+                *coord += (rest % 3) as i32 - 1;
+                rest /= 3;
+            }
+            if neighbor != cell {
+                f(neighbor);
+            }
+        }
+    }
+}
+
+impl Neighborhood<HexGrid> for Moore {
+    fn for_each_neighbor(&self, _: &HexGrid, (q, r): (i32, i32), f: impl FnMut((i32, i32))) {
+        const OFFSETS: [(i32, i32); 6] = [(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)];
+        OFFSETS.iter().map(|(dq, dr)| (q + dq, r + dr)).for_each(f);
+    }
+}
+
+/// The first cell in sight in each of the 8 directions, looking over the holes
+pub struct LineOfSight;
+
+impl Neighborhood<SquareGrid> for LineOfSight {
+    fn for_each_neighbor(&self, topology: &SquareGrid, cell: Pos, f: impl FnMut(Pos)) {
+        ALL_AROUND
+            .iter()
+            .filter_map(|&d| topology.is_cell.ray(cell, d).find(|&p| topology.is_cell(p)))
+            .for_each(f);
+    }
+}
+
+/// The numbers of live neighbors that make a dead cell live and keep a live cell alive
+#[derive(Debug, Clone)]
+pub struct Rule {
+    birth: Vec<usize>,
+    survival: Vec<usize>,
+}
+
+impl Rule {
+    pub fn new(birth: &[usize], survival: &[usize]) -> Self {
+        Rule {
+            birth: birth.to_vec(),
+            survival: survival.to_vec(),
+        }
+    }
+
+    fn lives(&self, alive: bool, live_neighbors: usize) -> bool {
+        match alive {
+            true => self.survival.contains(&live_neighbors),
+            false => self.birth.contains(&live_neighbors),
+        }
+    }
+}
+
+/// How [`Automaton::run`] ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The next generation is the same as the current one
+    Fixpoint,
+    /// The generations repeat forever, `period` generations each time
+    Cycle { period: usize },
+}
+
+pub struct Automaton<T: Topology, N> {
+    topology: T,
+    neighborhood: N,
+    rule: Rule,
+    live: FxHashSet<T::Cell>,
+    generation: usize,
+}
+
+impl<T: Topology, N: Neighborhood<T>> Automaton<T, N> {
+    pub fn new(
+        topology: T,
+        neighborhood: N,
+        rule: Rule,
+        live: impl IntoIterator<Item = T::Cell>,
+    ) -> Self {
+        Automaton {
+            topology,
+            neighborhood,
+            rule,
+            live: live.into_iter().collect(),
+            generation: 0,
+        }
+    }
+
+    pub fn live(&self) -> impl Iterator<Item = &T::Cell> {
+        self.live.iter()
+    }
+
+    pub fn is_live(&self, cell: &T::Cell) -> bool {
+        self.live.contains(cell)
+    }
+
+    pub fn live_count(&self) -> usize {
+        self.live.len()
+    }
+
+    /// The number of steps so far
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Makes the next generation, `false` if it is the same as the current one
+    pub fn step(&mut self) -> bool {
+        let mut live_neighbors = self.topology.counts(self.live.len());
+        for &cell in self.live.iter() {
+            self.neighborhood
+                .for_each_neighbor(&self.topology, cell, |n| live_neighbors.add(n));
+        }
+
+        let (rule, live) = (&self.rule, &self.live);
+        let mut next = FxHashSet::with_capacity_and_hasher(live.len(), Default::default());
+        live_neighbors.for_each(|cell, count| {
+            if rule.lives(live.contains(&cell), count) {
+                next.insert(cell);
+            }
+        });
+
+        // The cells without live neighbors aren't counted at all
+        if rule.lives(true, 0) {
+            next.extend(
+                live.iter()
+                    .filter(|&&c| live_neighbors.get(c) == 0)
+                    .copied(),
+            );
+        }
+        if rule.lives(false, 0) {
+            if let Some(cells) = self.topology.cells() {
+                next.extend(
+                    cells
+                        .into_iter()
+                        .filter(|&c| live_neighbors.get(c) == 0 && !live.contains(&c)),
+                );
+            }
+        }
+
+        let changed = next != self.live;
+        self.live = next;
+        self.generation += 1;
+        changed
+    }
+
+    pub fn steps(&mut self, count: usize) {
+        for _ in 0..count {
+            self.step();
+        }
+    }
+
+    /// Steps until the cells stop changing or repeat an earlier generation
+    ///
+    /// It keeps one earlier generation and replaces it at the generations 1, 2, 4, 8 and so on, so a cycle is found
+    /// at most twice as late as it starts (Brent's algorithm). The cells of an infinite topology may grow forever,
+    /// and then it never returns.
+    pub fn run(&mut self) -> Outcome {
+        let mut saved = (self.generation, self.live.clone());
+        let mut power = 1;
+
+        loop {
+            if !self.step() {
+                return Outcome::Fixpoint;
+            }
+
+            let (saved_generation, saved_live) = &saved;
+            if saved_live == &self.live {
+                return Outcome::Cycle {
+                    period: self.generation - saved_generation,
+                };
+            }
+            if self.generation - saved_generation == power {
+                saved = (self.generation, self.live.clone());
+                power *= 2;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn life() -> Rule {
+        Rule::new(&[3], &[2, 3])
+    }
+
+    #[test]
+    fn a_block_is_a_fixpoint() {
+        let block = vec![[0, 0], [0, 1], [1, 0], [1, 1]];
+        let mut automaton = Automaton::new(Lattice::<2>, Moore, life(), block);
+        assert_eq!(automaton.run(), Outcome::Fixpoint);
+        assert_eq!(automaton.live_count(), 4);
+    }
+
+    #[test]
+    fn a_blinker_is_a_cycle() {
+        let blinker = vec![[0, -1], [0, 0], [0, 1]];
+        let mut automaton = Automaton::new(Lattice::<2>, Moore, life(), blinker);
+        assert_eq!(automaton.run(), Outcome::Cycle { period: 2 });
+    }
+
+    #[test]
+    fn a_glider_moves() {
+        let glider = vec![[1, 0], [2, 1], [0, 2], [1, 2], [2, 2]];
+        let mut automaton = Automaton::new(Lattice::<2>, Moore, life(), glider.clone());
+        automaton.steps(4);
+
+        assert_eq!(automaton.live_count(), glider.len());
+        assert!(glider
+            .iter()
+            .all(|&[x, y]| automaton.is_live(&[x + 1, y + 1])));
+        assert_eq!(automaton.generation(), 4);
+    }
+
+    #[test]
+    fn the_holes_stay_dead() {
+        // Everything without live neighbors is born, except for the hole in the middle
+        let is_cell = Grid::from_fn(3, 3, |p| p != (1, 1));
+        let mut automaton =
+            Automaton::new(SquareGrid::new(is_cell), Moore, Rule::new(&[0], &[]), None);
+        automaton.step();
+        assert_eq!(automaton.live_count(), 8);
+    }
+
+    #[test]
+    fn sees_over_the_holes() {
+        let is_cell = Grid::from_fn(5, 1, |(_, col)| col % 2 == 0);
+        let topology = SquareGrid::new(is_cell);
+        let mut neighbors = vec![];
+        LineOfSight.for_each_neighbor(&topology, (0, 2), |n| neighbors.push(n));
+        assert_eq!(neighbors, [(0, 0), (0, 4)]);
+    }
+}
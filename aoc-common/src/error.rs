@@ -0,0 +1,246 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+
+/// A piece of the input that doesn't look the way we expect
+///
+/// The parsers only know the line they are parsing, so they create the error with [`ParseError::new`]
+/// and the callers fill in the line number with [`ParseError::at_line`] and the day with [`ParseError::in_day`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: Option<u8>,
+    /// 1-based
+    pub line: Option<usize>,
+    /// 1-based, in chars
+    pub column: usize,
+    pub kind: ParseErrorKind,
+    pub expected: String,
+    pub source_line: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The token is there, but it's not the expected one
+    Unexpected { found: String },
+    /// The line ends or the next token starts where we expect something else
+    Missing,
+}
+
+impl ParseError {
+    /// Creates the error for `token`, which must be a slice of `line`
+    ///
+    /// An empty `token` means that the expected thing is missing at its position.
+    pub fn new(line: &str, token: &str, expected: impl Into<String>) -> Self {
+        let offset = (token.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+        // If the token is not a part of the line after all, we just point to the start of the line
+        let column = line.get(..offset).map_or(0, |s| s.chars().count()) + 1;
+
+        let kind = match token.is_empty() {
+            true => ParseErrorKind::Missing,
+            false => ParseErrorKind::Unexpected {
+                found: token.to_string(),
+            },
+        };
+
+        ParseError {
+            day: None,
+            line: None,
+            column,
+            kind,
+            expected: expected.into(),
+            source_line: line.to_string(),
+        }
+    }
+
+    /// Creates the error for something missing right after `token`, which must be a slice of `line`
+    pub fn after(line: &str, token: &str, expected: impl Into<String>) -> Self {
+        // We may use the index here because the length is always a char boundary
+        ParseError::new(line, &token[token.len()..], expected)
+    }
+
+    /// Creates the error for `token`, which must be a slice of the lines of `block`, on the line of the token
+    ///
+    /// The line number is relative to the block, so the callers set the number of its first line with
+    /// [`ParseError::at_line`] as usual. A token across several lines is cut at the end of its first line.
+    pub fn in_block(block: &str, token: &str, expected: impl Into<String>) -> Self {
+        let offset = (token.as_ptr() as usize).wrapping_sub(block.as_ptr() as usize);
+        let before = match block.get(..offset) {
+            Some(before) => before,
+            // If the token is not a part of the block after all, we just point to the start of the block
+            None => return ParseError::new(block, token, expected).at_line(1),
+        };
+
+        let start = before.rfind('\n').map_or(0, |ix| ix + 1);
+        let end = block[offset..]
+            .find('\n')
+            .map_or(block.len(), |ix| offset + ix);
+        let line = block[start..end].trim_end_matches('\r');
+        // We may use the indices here, because the token starts at a char boundary of the line
+        let token =
+            &block[offset..offset + token.len().min((start + line.len()).saturating_sub(offset))];
+
+        ParseError::new(line, token, expected).at_line(before.matches('\n').count() + 1)
+    }
+
+    /// Moves the error of `token`, which was parsed on its own, to the line of the token in `block`
+    ///
+    /// The error must be about `token` itself, i.e. created with `token` as its line.
+    pub fn within(self, block: &str, token: &str) -> Self {
+        let start = token
+            .char_indices()
+            .nth(self.column - 1)
+            .map_or(token.len(), |(ix, _)| ix);
+        let len = match &self.kind {
+            ParseErrorKind::Unexpected { found } => found.len(),
+            ParseErrorKind::Missing => 0,
+        };
+        let found = token.get(start..start + len).unwrap_or(&token[start..]);
+
+        ParseError::in_block(block, found, self.expected)
+    }
+
+    /// Sets the line number
+    ///
+    /// If the line number is already set, it is relative to a block of lines (e.g. a group)
+    /// and `line` is the number of the first line of this block.
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(self.line.map_or(line, |relative| line + relative - 1));
+        self
+    }
+
+    pub fn in_day(mut self, day: u8) -> Self {
+        self.day = Some(day);
+        self
+    }
+
+    /// The first line of the error without the source line and the caret
+    pub fn message(&self) -> String {
+        let mut message = String::new();
+        if let Some(day) = self.day {
+            message += &format!("Day {}, ", day);
+        }
+        if let Some(line) = self.line {
+            message += &format!("line {}, ", line);
+        }
+        message += &format!("column {}: expected {}, ", self.column, self.expected);
+        match &self.kind {
+            ParseErrorKind::Unexpected { found } => message += &format!("found {:?}", found),
+            ParseErrorKind::Missing => message += "found nothing",
+        }
+        message
+    }
+}
+
+/// Sets the day of the [`ParseError`] inside `error`, if it's there
+pub fn in_day(mut error: anyhow::Error, day: u8) -> anyhow::Error {
+    if let Some(parse_error) = error.downcast_mut::<ParseError>() {
+        parse_error.day = Some(day);
+    }
+    error
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.message())?;
+
+        let number = self.line.map(|l| l.to_string()).unwrap_or_default();
+        let underline = match &self.kind {
+            ParseErrorKind::Unexpected { found } => found.chars().count(),
+            ParseErrorKind::Missing => 1,
+        };
+        writeln!(f, "{} | {}", number, self.source_line)?;
+        write!(
+            f,
+            "{} | {}{}",
+            " ".repeat(number.len()),
+            " ".repeat(self.column - 1),
+            "^".repeat(underline)
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_column_of_the_token() {
+        let line = "nop +0\nacc +1";
+        let line = line.lines().nth(1).unwrap();
+        let error = ParseError::new(line, &line[4..], "a number").at_line(2);
+        assert_eq!(error.column, 5);
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::Unexpected {
+                found: "+1".to_string()
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 5: expected a number, found \"+1\"\n2 | acc +1\n  |     ^^"
+        );
+    }
+
+    #[test]
+    fn points_after_the_token() {
+        let line = "jmp";
+        let error = ParseError::after(line, line, "an argument").in_day(8);
+        assert_eq!(error.column, 4);
+        assert_eq!(error.kind, ParseErrorKind::Missing);
+        assert_eq!(
+            error.to_string(),
+            "Day 8, column 4: expected an argument, found nothing\n | jmp\n |    ^"
+        );
+    }
+
+    #[test]
+    fn counts_columns_in_chars() {
+        let line = "ñañ x";
+        let error = ParseError::new(line, &line[6..], "y");
+        assert_eq!(error.column, 5);
+    }
+
+    #[test]
+    fn finds_the_line_in_a_block() {
+        let block = "eyr:2029 byr:1980\r\nhgt:59cm pid:021572410";
+        let error = ParseError::in_block(block, &block[23..27], "a height").at_line(4);
+        assert_eq!((error.line, error.column), (Some(5), 5));
+        assert_eq!(error.source_line, "hgt:59cm pid:021572410");
+
+        let error = ParseError::in_block(block, &block[13..], "a year");
+        assert_eq!((error.line, error.column), (Some(1), 14));
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::Unexpected {
+                found: "1980".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn moves_the_error_into_the_block() {
+        let block = "eyr:2029\nhgt:59xx pid:021572410";
+        let value = &block[13..17];
+        let error = ParseError::new(value, &value[2..], "cm or in").within(block, value);
+        assert_eq!((error.line, error.column), (Some(2), 7));
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::Unexpected {
+                found: "xx".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn adds_relative_line_numbers() {
+        let error = ParseError::new("", "", "x").at_line(3).at_line(10);
+        assert_eq!(error.line, Some(12));
+    }
+
+    #[test]
+    fn sets_the_day_through_anyhow() {
+        let error = in_day(ParseError::new("", "", "x").into(), 5);
+        assert_eq!(error.downcast_ref::<ParseError>().unwrap().day, Some(5));
+    }
+}
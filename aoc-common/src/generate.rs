@@ -0,0 +1,49 @@
+//! Seeded synthetic puzzle inputs for the stress tests
+//!
+//! Every day has a `generate` module with a [`Generate`] function, the `PUZZLE_SCALE` of the real input and the
+//! `SCALES` it can make. The meaning of the scale is up to the day: the number of lines, the side of a grid, the
+//! number of cards... The runner rejects the scales out of `SCALES`, and the generators clamp them, e.g. in the tests.
+
+use crate::{Answer, Params, Part};
+use anyhow::{bail, Context, Result};
+use rand::SeedableRng;
+use rand_pcg::Pcg64;
+
+/// Makes a valid puzzle input from the seed, the same one for the same seed and scale
+pub type Generate = fn(seed: u64, scale: usize) -> Generated;
+
+pub struct Generated {
+    pub input: String,
+    /// One per part, `None` if the answer can't be found without solving the puzzle the same way the day does
+    pub answers: Vec<Option<Answer>>,
+    /// The options the parts need to solve this input, e.g. its size if it's not the size of the real input
+    pub params: Params,
+}
+
+impl Generated {
+    /// Solves the input with the parts and its options and compares their answers with the known ones
+    ///
+    /// The parts without a known answer are skipped, and so are the extra answers if there are fewer parts.
+    pub fn verify(&self, parts: &[Part]) -> Result<()> {
+        for (ix, (part, expected)) in parts.iter().zip(&self.answers).enumerate() {
+            if let Some(expected) = expected {
+                let answer = part(&self.input, &self.params)
+                    .with_context(|| format!("Part {} failed", ix + 1))?;
+
+                // The day may return another integer type than the generator, so we compare the text
+                if answer.to_string() != expected.to_string() {
+                    bail!("Part {}: expected {}, found {}", ix + 1, expected, answer);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The random number generator of the generators
+///
+/// Unlike `StdRng`, PCG is guaranteed to give the same numbers for the same seed on every platform and version.
+pub fn rng(seed: u64) -> Pcg64 {
+    Pcg64::seed_from_u64(seed)
+}
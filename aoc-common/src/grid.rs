@@ -0,0 +1,329 @@
+//! A rectangular grid of cells for the days with a 2D map
+
+use crate::ParseError;
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut, Range};
+
+/// A cell position, `(row, column)` from the top left corner
+pub type Pos = (usize, usize);
+
+/// A step between the cells, `(rows, columns)`
+pub type Direction = (isize, isize);
+
+/// Up, left, right and down
+pub const ORTHOGONAL: [Direction; 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// The orthogonal and the diagonal directions, row by row
+pub const ALL_AROUND: [Direction; 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// The cells are stored row by row, so all the rows have the same width
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Makes each cell from its position
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(&mut cell)
+            .collect();
+
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Parses a map with one char per cell, `cell` gives `None` for the chars that aren't `expected`
+    ///
+    /// The map must have at least one row, and all the rows must be as wide as the first one.
+    pub fn parse(
+        input: &str,
+        cell: impl Fn(char) -> Option<T>,
+        expected: &str,
+    ) -> Result<Self, ParseError> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+
+        for (ix, line) in input.lines().enumerate() {
+            let line_n = ix + 1;
+            let mut count = 0;
+
+            for (char_ix, char) in line.char_indices() {
+                let token = &line[char_ix..char_ix + char.len_utf8()];
+                if width == Some(count) {
+                    let error = ParseError::new(line, &line[char_ix..], "the end of the row");
+                    return Err(error.at_line(line_n));
+                }
+                match cell(char) {
+                    Some(cell) => cells.push(cell),
+                    None => return Err(ParseError::new(line, token, expected).at_line(line_n)),
+                }
+                count += 1;
+            }
+
+            match width {
+                // An empty row would make an empty grid that has rows
+                None if count == 0 => {
+                    return Err(ParseError::new(line, line, expected).at_line(line_n))
+                }
+                None => width = Some(count),
+                Some(width) if count < width => {
+                    return Err(ParseError::after(line, line, expected).at_line(line_n))
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) => Ok(Grid {
+                cells,
+                width,
+                height,
+            }),
+            None => Err(ParseError::new(input, input, "a row of the map").at_line(1)),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (row, col): Pos) -> Option<&T> {
+        match row < self.height && col < self.width {
+            true => self.cells.get(row * self.width + col),
+            false => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, (row, col): Pos) -> Option<&mut T> {
+        match row < self.height && col < self.width {
+            true => self.cells.get_mut(row * self.width + col),
+            false => None,
+        }
+    }
+
+    /// The cell in the row, with the columns repeating to the right forever
+    pub fn get_wrapping(&self, (row, col): Pos) -> Option<&T> {
+        self.get((row, col.checked_rem(self.width)?))
+    }
+
+    /// The position one step away, if it's inside the grid
+    pub fn step(&self, (row, col): Pos, (rows, cols): Direction) -> Option<Pos> {
+        let row = offset(row, rows).filter(|&r| r < self.height)?;
+        let col = offset(col, cols).filter(|&c| c < self.width)?;
+        Some((row, col))
+    }
+
+    /// The positions next to `pos` in the [`ORTHOGONAL`] directions
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ORTHOGONAL.iter().filter_map(move |&d| self.step(pos, d))
+    }
+
+    /// The positions next to `pos` in [`ALL_AROUND`] directions
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ALL_AROUND.iter().filter_map(move |&d| self.step(pos, d))
+    }
+
+    /// The positions from `pos` in the direction up to the edge, without `pos` itself
+    pub fn ray(&self, pos: Pos, direction: Direction) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(self.step(pos, direction), move |&p| self.step(p, direction))
+    }
+
+    /// All the positions row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// All the cells row by row
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // A grid without columns has no cells, so any chunk size gives no rows
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(col).step_by(self.width.max(1))
+    }
+
+    pub fn map<U>(&self, cell: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(cell).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, cell: T) -> Self {
+        Grid {
+            cells: vec![cell; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Rotated counterclockwise, so the right column becomes the top row
+    pub fn rotated(&self) -> Self {
+        Grid::from_fn(self.height, self.width, |(row, col)| {
+            self[(col, self.width - 1 - row)].clone()
+        })
+    }
+
+    /// Flipped over the main diagonal, so the rows become the columns
+    pub fn transposed(&self) -> Self {
+        Grid::from_fn(self.height, self.width, |(row, col)| {
+            self[(col, row)].clone()
+        })
+    }
+
+    /// Flipped left to right
+    pub fn flipped(&self) -> Self {
+        Grid::from_fn(self.width, self.height, |(row, col)| {
+            self[(row, self.width - 1 - col)].clone()
+        })
+    }
+
+    /// A copy of the rectangle, which must be inside the grid
+    pub fn sub_grid(&self, rows: Range<usize>, cols: Range<usize>) -> Self {
+        Grid::from_fn(cols.len(), rows.len(), |(row, col)| {
+            self[(rows.start + row, cols.start + col)].clone()
+        })
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!(
+                "{:?} is outside of the {}x{} grid",
+                pos, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("{:?} is outside of the {}x{} grid", pos, width, height),
+        }
+    }
+}
+
+fn offset(value: usize, by: isize) -> Option<usize> {
+    match by < 0 {
+        true => value.checked_sub(by.unsigned_abs()),
+        false => value.checked_add(by as usize),
+    }
+}
+
+/// One line per row with the cells side by side
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(input: &str) -> Grid<char> {
+        Grid::parse(input, Some, "a cell").unwrap()
+    }
+
+    #[test]
+    fn parses_and_renders() {
+        let map = "ab\ncd\nef\n";
+        let grid = grid(map);
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!(grid[(2, 0)], 'e');
+        assert_eq!(grid.to_string(), map);
+    }
+
+    #[test]
+    fn reports_bad_cells_and_rows() {
+        let parse = |input| Grid::parse(input, |c| Some(c).filter(|&c| c != 'x'), "a cell");
+
+        let error = parse("ab\nxb\n").unwrap_err();
+        assert_eq!((error.line, error.column), (Some(2), 1));
+
+        let error = parse("ab\nabc\n").unwrap_err();
+        assert_eq!((error.line, error.column), (Some(2), 3));
+
+        let error = parse("ab\na\n").unwrap_err();
+        assert_eq!((error.line, error.column), (Some(2), 2));
+
+        assert!(parse("").is_err());
+        assert!(parse("\nab\n").is_err());
+    }
+
+    #[test]
+    fn steps_inside_the_grid_only() {
+        let grid = grid("abc\ndef\n");
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbors8((1, 1)).count(), 5);
+        assert_eq!(
+            grid.ray((0, 0), (0, 1)).collect::<Vec<_>>(),
+            [(0, 1), (0, 2)]
+        );
+        assert_eq!(grid.ray((1, 2), (-1, -1)).collect::<Vec<_>>(), [(0, 1)]);
+        assert_eq!(grid.get_wrapping((1, 7)), Some(&'e'));
+        assert_eq!(grid.get((2, 0)), None);
+    }
+
+    #[test]
+    fn rotates_and_flips() {
+        let grid = grid("abc\ndef\n");
+        assert_eq!(grid.rotated().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.transposed().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.flipped().to_string(), "cba\nfed\n");
+        assert_eq!(grid.rotated().rotated().rotated().rotated(), grid);
+        assert_eq!(grid.sub_grid(0..2, 1..3).to_string(), "bc\nef\n");
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+    }
+}
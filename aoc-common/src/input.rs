@@ -0,0 +1,16 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Reads the whole puzzle input from stdin
+pub fn read_stdin() -> Result<String> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    Ok(input)
+}
+
+/// Reads the whole puzzle input from a file
+pub fn read_file(path: &Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("Can't read {}", path.display()))
+}
@@ -0,0 +1,107 @@
+//! Code shared between the days
+
+pub mod automaton;
+pub mod generate;
+pub mod grid;
+pub mod input;
+pub mod number_theory;
+pub mod params;
+pub mod parse;
+pub mod stream;
+pub mod trace;
+
+mod answer;
+mod error;
+
+pub use answer::Answer;
+pub use error::{in_day, ParseError, ParseErrorKind};
+pub use params::{Param, Params};
+
+use anyhow::Result;
+use std::io::{self, Write};
+
+/// One part of the puzzle solution with the answer type erased
+pub type Part = fn(&str, &Params) -> Result<Answer>;
+
+/// Turns the typed `part1`/`part2` functions of a day into a slice of [`Part`]s
+///
+/// The days with parameters pass their `part1_with`/`part2_with` functions after `with`, the other days don't
+/// get the parameters at all.
+///
+/// ```
+/// # use anyhow::Result;
+/// # use aoc_common::{Param, Params};
+/// const LINES: Param = Param { name: "lines", default: "1", help: "The lines to skip" };
+///
+/// fn part1(input: &str) -> Result<usize> {
+///     Ok(input.lines().count())
+/// }
+///
+/// fn part2_with(input: &str, params: &Params) -> Result<usize> {
+///     Ok(input.lines().skip(params.get(&LINES)?).count())
+/// }
+///
+/// let parts: &[aoc_common::Part] = aoc_common::parts![part1];
+/// assert_eq!(parts[0]("1\n2\n", &Params::default())?.to_string(), "2");
+///
+/// let parts: &[aoc_common::Part] = aoc_common::parts![with part2_with];
+/// assert_eq!(parts[0]("1\n2\n", &Params::default())?.to_string(), "1");
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[macro_export]
+macro_rules! parts {
+    (with $($part:path),+ $(,)?) => {
+        &[$(|input: &str, params: &$crate::Params| $part(input, params).map($crate::Answer::from)),+]
+    };
+    ($($part:path),+ $(,)?) => {
+        &[$(|input: &str, _: &$crate::Params| $part(input).map($crate::Answer::from)),+]
+    };
+}
+
+/// The parsing step of a day with the parsed data thrown away
+///
+/// The parts parse the input themselves, so this is only useful to time the parsing alone.
+pub type Parse = fn(&str) -> Result<()>;
+
+/// Turns the `parse` function of a day into a [`Parse`]
+///
+/// ```
+/// # use anyhow::Result;
+/// fn parse(input: &str) -> Result<Vec<u32>> {
+///     Ok(input.lines().map(str::parse).collect::<Result<_, _>>()?)
+/// }
+///
+/// let parse: aoc_common::Parse = aoc_common::parser!(parse);
+/// assert!(parse("1\n2\n").is_ok());
+/// assert!(parse("x").is_err());
+/// ```
+#[macro_export]
+macro_rules! parser {
+    ($parse:path) => {
+        |input: &str| $parse(input).map(drop)
+    };
+}
+
+/// Reads the puzzle input from stdin, runs the given parts of the day on it one by one and prints the answers
+///
+/// The command-line arguments are the options of the day's `params`, and the debug output of the day is
+/// switched on with the [`trace::ENV_VAR`] variable.
+pub fn run(day: u8, parts: &[Part], params: &[Param]) -> Result<()> {
+    trace::init(false)?;
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let params = Params::from_args(&args, params)?;
+    let input = input::read_stdin()?;
+
+    for part in parts {
+        let answer = part(&input, &params).map_err(|e| in_day(e, day))?;
+        print_answer(&answer)?;
+    }
+
+    Ok(())
+}
+
+/// Prints the answer of one part on its own line
+pub fn print_answer(answer: &Answer) -> Result<()> {
+    writeln!(io::stdout(), "{}", answer)?;
+    Ok(())
+}
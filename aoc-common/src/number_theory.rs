@@ -0,0 +1,208 @@
+//! Modular arithmetic for the days with the big numbers
+//!
+//! The numbers are `u64` and the intermediate products go through `u128`, so nothing here overflows.
+
+use std::collections::HashMap;
+
+/// `a * b % modulus`
+pub fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    (u128::from(a) * u128::from(b) % u128::from(modulus)) as u64
+}
+
+/// `base` to the power of `exp`, modulo `modulus`
+///
+/// https://en.wikipedia.org/wiki/Modular_exponentiation
+pub fn pow_mod(mut base: u64, mut exp: u64, modulus: u64) -> u64 {
+    let mut result = 1 % modulus;
+    base %= modulus;
+    while exp > 0 {
+        if exp % 2 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        exp >>= 1;
+        base = mul_mod(base, base, modulus);
+    }
+    result
+}
+
+/// The greatest common divisor `d` of `a` and `b` with the coefficients `x` and `y` such that `a * x + b * y == d`
+///
+/// https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm
+pub fn egcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        let next = (
+            old_r - quotient * r,
+            old_x - quotient * x,
+            old_y - quotient * y,
+        );
+        old_r = r;
+        old_x = x;
+        old_y = y;
+        r = next.0;
+        x = next.1;
+        y = next.2;
+    }
+
+    match old_r < 0 {
+        true => (-old_r, -old_x, -old_y),
+        false => (old_r, old_x, old_y),
+    }
+}
+
+/// The `x` such that `a * x % modulus == 1`, if `a` and `modulus` are coprime
+pub fn inverse_mod(a: u64, modulus: u64) -> Option<u64> {
+    let (d, x, _) = egcd(i128::from(a), i128::from(modulus));
+    match d == 1 {
+        // The remainder is less than the modulus, so it fits
+        true => Some(x.rem_euclid(i128::from(modulus)) as u64),
+        false => None,
+    }
+}
+
+/// The smallest `x` with `x % modulus == residue` for every `(residue, modulus)`, and the period of the solutions
+///
+/// The moduli don't have to be coprime. It's `None` if there is no such `x` or if the period, the least common
+/// multiple of the moduli, doesn't fit into `u64`.
+///
+/// https://en.wikipedia.org/wiki/Chinese_remainder_theorem#Generalization_to_non-coprime_moduli
+pub fn crt(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(x, period), &(residue, modulus)| {
+            if modulus == 0 {
+                return None;
+            }
+            let residue = residue % modulus;
+
+            let (d, _, _) = egcd(i128::from(period), i128::from(modulus));
+            let d = d as u64;
+            // We need x + period * k == residue (mod modulus), which only has a solution if d divides the difference
+            let difference =
+                (i128::from(residue) - i128::from(x)).rem_euclid(i128::from(modulus)) as u64;
+            if difference / d * d != difference {
+                return None;
+            }
+
+            let step = modulus / d;
+            // We may .unwrap() here, because period / d and step are coprime after we divided by the common divisor
+            let k = mul_mod(
+                difference / d,
+                inverse_mod(period / d % step, step).unwrap(),
+                step,
+            );
+            let new_period = (period / d).checked_mul(modulus)?;
+            // x < period and k < step, so the sum fits into u128
+            let new_x =
+                (u128::from(x) + u128::from(period) * u128::from(k)) % u128::from(new_period);
+
+            Some((new_x as u64, new_period))
+        })
+}
+
+/// The biggest modulus of [`discrete_log`], which keeps its table at 2 million powers, about 64 MiB
+pub const MAX_LOG_MODULUS: u64 = 1 << 42;
+
+/// The smallest `x` such that `base` to the power of `x` is `target` modulo `modulus`
+///
+/// The `base` must be coprime with the `modulus`, otherwise it's `None` like when there's no such `x`. It's `None`
+/// for a `modulus` above [`MAX_LOG_MODULUS`] too, because the table of the square root of it doesn't fit into memory.
+///
+/// https://en.wikipedia.org/wiki/Baby-step_giant-step
+pub fn discrete_log(base: u64, target: u64, modulus: u64) -> Option<u64> {
+    if modulus == 1 {
+        return Some(0);
+    }
+    if modulus > MAX_LOG_MODULUS {
+        return None;
+    }
+    let target = target % modulus;
+
+    // Every power repeats after fewer than modulus steps, so steps * steps powers are enough
+    // The root is rounded up, so it's the root of one less rounded down plus one
+    let steps = (modulus - 1).isqrt() + 1;
+
+    // The baby steps: base to the power of j for j < steps, keeping the smallest j for each power
+    let mut baby_steps = HashMap::new();
+    let mut power = 1;
+    for j in 0..steps {
+        baby_steps.entry(power).or_insert(j);
+        power = mul_mod(power, base, modulus);
+    }
+
+    // The giant steps: target divided by base to the power of steps, i times
+    let giant_step = inverse_mod(pow_mod(base, steps, modulus), modulus)?;
+    let mut value = target;
+    for i in 0..steps {
+        if let Some(j) = baby_steps.get(&value) {
+            return Some(i * steps + j);
+        }
+        value = mul_mod(value, giant_step, modulus);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiplies_without_overflow() {
+        assert_eq!(mul_mod(u64::MAX - 1, u64::MAX - 1, u64::MAX), 1);
+        assert_eq!(pow_mod(2, 64, u64::MAX), 1);
+        assert_eq!(pow_mod(7, 8, 20201227), 5764801);
+        assert_eq!(pow_mod(5, 0, 1), 0);
+    }
+
+    #[test]
+    fn finds_the_bezout_coefficients() {
+        assert_eq!(egcd(240, 46), (2, -9, 47));
+        assert_eq!(egcd(-4, 6).0, 2);
+        assert_eq!(egcd(0, 0), (0, 1, 0));
+    }
+
+    #[test]
+    fn inverts() {
+        assert_eq!(inverse_mod(3, 11), Some(4));
+        assert_eq!(inverse_mod(6, 9), None);
+        assert_eq!(inverse_mod(1, 1), Some(0));
+    }
+
+    #[test]
+    fn solves_the_congruences() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // Not coprime, but consistent
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(1, u64::MAX), (1, u64::MAX - 1)]), None);
+    }
+
+    #[test]
+    fn finds_the_logarithm() {
+        assert_eq!(discrete_log(7, 5764801, 20201227), Some(8));
+        assert_eq!(discrete_log(7, 17807724, 20201227), Some(11));
+        assert_eq!(discrete_log(3, 1, 7), Some(0));
+        // The powers of 2 modulo 7 are 1, 2 and 4 only
+        assert_eq!(discrete_log(2, 3, 7), None);
+        assert_eq!(discrete_log(2, 4, 8), None);
+    }
+
+    #[test]
+    fn takes_the_square_root_of_the_modulus() {
+        // 2 has the order 20 modulo 25, and the 5 baby steps and 5 giant steps reach the last power exactly
+        assert_eq!(discrete_log(2, pow_mod(2, 19, 25), 25), Some(19));
+        let prime = 1_000_000_007;
+        assert_eq!(
+            discrete_log(5, pow_mod(5, 999_999_999, prime), prime),
+            Some(999_999_999)
+        );
+
+        assert_eq!(discrete_log(3, 1, MAX_LOG_MODULUS + 1), None);
+    }
+}
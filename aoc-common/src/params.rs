@@ -0,0 +1,148 @@
+//! The puzzle parameters that can be changed from the command line
+//!
+//! A day declares its parameters as [`Param`]s with the values of the real puzzle as the defaults, and reads them
+//! from [`Params`], so a variant of the puzzle is just `--preamble 5` away.
+
+use anyhow::{anyhow, bail, Context, Result};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// A named option of a day, like `--preamble 25`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub help: &'static str,
+}
+
+/// The values given to the parameters, the others keep their defaults
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Params {
+    values: BTreeMap<&'static str, String>,
+}
+
+impl Params {
+    /// Parses the `--name value` and `--name=value` options, which must be `known`
+    pub fn from_args(args: &[String], known: &[Param]) -> Result<Self> {
+        let mut params = Params::default();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            let option = arg
+                .strip_prefix("--")
+                .ok_or_else(|| anyhow!("Unexpected argument {:?}\n{}", arg, usage(known)))?;
+            let (name, value) = match option.find('=') {
+                Some(ix) => (&option[..ix], option[ix + 1..].to_string()),
+                None => match args.next() {
+                    Some(value) => (option, value.clone()),
+                    None => bail!("The option --{} needs a value", option),
+                },
+            };
+
+            match known.iter().find(|p| p.name == name) {
+                Some(param) => params = params.with(param, value),
+                None => bail!("Unknown option --{}\n{}", name, usage(known)),
+            }
+        }
+
+        Ok(params)
+    }
+
+    pub fn with(mut self, param: &Param, value: impl ToString) -> Self {
+        self.values.insert(param.name, value.to_string());
+        self
+    }
+
+    /// Whether every parameter keeps its default
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The value of the parameter, or its default if it wasn't given
+    pub fn get<T>(&self, param: &Param) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Error + Send + Sync + 'static,
+    {
+        let value = self
+            .values
+            .get(param.name)
+            .map_or(param.default, String::as_str);
+
+        value
+            .parse()
+            .with_context(|| format!("Invalid value {:?} for --{}", value, param.name))
+    }
+}
+
+/// The values as the command line options that give them, e.g. `--preamble 5`
+impl Display for Params {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let options = self
+            .values
+            .iter()
+            .map(|(name, value)| format!("--{} {}", name, value))
+            .collect::<Vec<_>>();
+        write!(f, "{}", options.join(" "))
+    }
+}
+
+/// The list of the parameters with their defaults, for the errors
+pub fn usage(params: &[Param]) -> String {
+    if params.is_empty() {
+        return "This day has no options".to_string();
+    }
+
+    let lines = params
+        .iter()
+        .map(|p| format!("  --{} <{}>  {}", p.name, p.default, p.help))
+        .collect::<Vec<_>>();
+    format!("The options are:\n{}", lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PREAMBLE: Param = Param {
+        name: "preamble",
+        default: "25",
+        help: "The number of the previous numbers",
+    };
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn falls_back_to_the_defaults() -> Result<()> {
+        let params = Params::from_args(&[], &[PREAMBLE])?;
+        assert_eq!(params.get::<usize>(&PREAMBLE)?, 25);
+        Ok(())
+    }
+
+    #[test]
+    fn parses_both_forms() -> Result<()> {
+        let params = Params::from_args(&args(&["--preamble", "5"]), &[PREAMBLE])?;
+        assert_eq!(params.get::<usize>(&PREAMBLE)?, 5);
+
+        let params = Params::from_args(&args(&["--preamble=7"]), &[PREAMBLE])?;
+        assert_eq!(params.get::<usize>(&PREAMBLE)?, 7);
+        assert_eq!(params.to_string(), "--preamble 7");
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_the_bad_options() {
+        assert!(Params::from_args(&args(&["--preamble"]), &[PREAMBLE]).is_err());
+        assert!(Params::from_args(&args(&["5"]), &[PREAMBLE]).is_err());
+
+        let error = Params::from_args(&args(&["--year", "2021"]), &[PREAMBLE]).unwrap_err();
+        assert!(error.to_string().contains("--preamble <25>"));
+
+        let params = Params::default().with(&PREAMBLE, "many");
+        assert!(params.get::<usize>(&PREAMBLE).is_err());
+    }
+}
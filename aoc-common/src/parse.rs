@@ -0,0 +1,170 @@
+use crate::ParseError;
+use std::io::{Error, ErrorKind, Result};
+use std::str::FromStr;
+
+/// The error we return when the input doesn't look the way we expect
+pub fn invalid_data() -> Error {
+    Error::from(ErrorKind::InvalidData)
+}
+
+/// Turns a missing value into the [`invalid_data`] error
+///
+/// It replaces `.ok_or_else(|| Error::from(ErrorKind::InvalidData))` that we use everywhere
+/// when something we expect to find in the input is absent.
+pub trait OrInvalid<T> {
+    fn or_invalid(self) -> Result<T>;
+}
+
+impl<T> OrInvalid<T> for Option<T> {
+    fn or_invalid(self) -> Result<T> {
+        self.ok_or_else(invalid_data)
+    }
+}
+
+/// Parses `token`, which must be a slice of `line`, or reports what we `expected` there
+pub fn parse_token<T: FromStr>(
+    line: &str,
+    token: &str,
+    expected: &str,
+) -> std::result::Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::new(line, token, expected))
+}
+
+/// Parses each line of the input, adding the line number to the error
+pub fn parse_lines<T>(
+    input: &str,
+    parse: impl Fn(&str) -> std::result::Result<T, ParseError>,
+) -> std::result::Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(ix, line)| parse(line).map_err(|e| e.at_line(ix + 1)))
+        .collect()
+}
+
+/// Splits the input into groups of lines separated by blank lines
+///
+/// Works with `\n`, `\r\n` and mixed line endings. Lines consisting of whitespace only
+/// are treated as blank, so trailing whitespace at the end of the input doesn't produce an empty group.
+/// Each group is returned without its trailing line ending, so `.lines()` can be used on it.
+pub fn split_groups(input: &str) -> Groups<'_> {
+    Groups {
+        rest: input,
+        line: 1,
+    }
+}
+
+/// The same as [`split_groups`], but also gives the number of the first line of each group
+pub fn split_numbered_groups(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut groups = split_groups(input);
+    std::iter::from_fn(move || groups.next_numbered())
+}
+
+pub struct Groups<'a> {
+    rest: &'a str,
+    /// The number of the first line of `rest`
+    line: usize,
+}
+
+impl<'a> Iterator for Groups<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_numbered().map(|(_, group)| group)
+    }
+}
+
+impl<'a> Groups<'a> {
+    fn next_numbered(&mut self) -> Option<(usize, &'a str)> {
+        let mut start = None;
+        let mut end = 0;
+        let mut consumed = 0;
+
+        for line in self.rest.split_inclusive('\n') {
+            let blank = line.trim().is_empty();
+
+            if blank && start.is_some() {
+                break;
+            }
+
+            if !blank && start.is_none() {
+                start = Some((self.line, consumed));
+            }
+
+            consumed += line.len();
+            self.line += 1;
+
+            if !blank {
+                end = consumed - (line.len() - line.trim_end_matches(&['\r', '\n'][..]).len());
+            }
+        }
+
+        // We may use the indexes here because they point to the line boundaries inside self.rest
+        let group = start.map(|(line, start)| (line, &self.rest[start..end]));
+        self.rest = &self.rest[consumed..];
+        group
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn groups(input: &str) -> Vec<&str> {
+        split_groups(input).collect()
+    }
+
+    #[test]
+    fn or_invalid() {
+        assert_eq!(Some(1).or_invalid().ok(), Some(1));
+        assert_eq!(
+            None::<u8>.or_invalid().map_err(|e| e.kind()),
+            Err(ErrorKind::InvalidData)
+        );
+    }
+
+    #[test]
+    fn splits_lf() {
+        assert_eq!(groups("a\nb\n\nc\n"), vec!["a\nb", "c"]);
+    }
+
+    #[test]
+    fn splits_crlf() {
+        assert_eq!(groups("a\r\nb\r\n\r\nc\r\n"), vec!["a\r\nb", "c"]);
+    }
+
+    #[test]
+    fn splits_mixed_endings() {
+        assert_eq!(groups("a\r\nb\n\r\nc\n\nd"), vec!["a\r\nb", "c", "d"]);
+    }
+
+    #[test]
+    fn ignores_extra_blank_lines_and_trailing_whitespace() {
+        assert_eq!(groups("\n\na\n\n\n \t\nb\n  \n\r\n"), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn numbers_groups_by_their_first_line() {
+        let groups = split_numbered_groups("\na\nb\r\n\r\n\nc\n").collect::<Vec<_>>();
+        assert_eq!(groups, vec![(2, "a\nb"), (6, "c")]);
+    }
+
+    #[test]
+    fn parses_lines_with_line_numbers() {
+        let input = "1\n2\nx\n";
+        let error = parse_lines(input, |l| parse_token::<u8>(l, l, "a number")).unwrap_err();
+        assert_eq!(error.line, Some(3));
+        assert_eq!(
+            parse_lines(input.get(..4).unwrap(), |l| parse_token(l, l, "")),
+            Ok(vec![1_u8, 2])
+        );
+    }
+
+    #[test]
+    fn empty_input_has_no_groups() {
+        assert!(groups("").is_empty());
+        assert!(groups(" \r\n\n").is_empty());
+    }
+}
@@ -0,0 +1,150 @@
+//! Reading the inputs line by line, for the generated inputs that don't fit into memory
+//!
+//! The line-oriented days have a `stream` module with the same parts as the crate root, but they take a [`Source`]
+//! and keep only what the puzzle needs, not the whole input.
+
+use crate::{Answer, Params, Part};
+use anyhow::{bail, Context, Result};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether a part has already read stdin, which can't start over
+static STDIN_OPENED: AtomicBool = AtomicBool::new(false);
+
+/// The input of a streaming part, which it may read from the start as many times as it needs, except for stdin
+#[derive(Debug, Clone, Copy)]
+pub enum Source<'a> {
+    Text(&'a str),
+    File(&'a Path),
+    /// Can be opened only once in the whole process
+    Stdin,
+}
+
+impl<'a> Source<'a> {
+    pub fn open(self) -> Result<Box<dyn BufRead + 'a>> {
+        match self {
+            Source::Text(text) => Ok(Box::new(text.as_bytes())),
+            Source::File(path) => {
+                let file =
+                    File::open(path).with_context(|| format!("Can't read {}", path.display()))?;
+                Ok(Box::new(BufReader::new(file)))
+            }
+            Source::Stdin => match STDIN_OPENED.swap(true, Ordering::SeqCst) {
+                true => bail!("Can't read stdin again, the input must be a file for that"),
+                false => Ok(Box::new(io::stdin().lock())),
+            },
+        }
+    }
+
+    /// Whether the source can be opened again, so the parts that read it twice check it before the first pass
+    pub fn rereadable(self) -> bool {
+        !matches!(self, Source::Stdin)
+    }
+
+    /// The lines with their 1-based numbers, one at a time and without the line endings like `str::lines`
+    pub fn lines(self) -> Result<impl Iterator<Item = Result<(usize, String)>> + 'a> {
+        Ok(self.open()?.lines().enumerate().map(|(ix, line)| {
+            let line = line.with_context(|| format!("Can't read line {}", ix + 1))?;
+            Ok((ix + 1, line))
+        }))
+    }
+}
+
+/// One streaming part of the puzzle solution with the answer type erased
+pub type StreamPart = fn(Source<'_>, &Params) -> Result<Answer>;
+
+/// The same as [`parts!`](crate::parts), but for the streaming parts
+///
+/// ```
+/// # use anyhow::Result;
+/// # use aoc_common::stream::{Source, StreamPart};
+/// # use aoc_common::Params;
+/// fn part1(source: Source<'_>) -> Result<usize> {
+///     Ok(source.lines()?.count())
+/// }
+///
+/// let parts: &[StreamPart] = aoc_common::stream_parts![part1];
+/// assert_eq!(parts[0](Source::Text("1\n2\n"), &Params::default())?.to_string(), "2");
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+#[macro_export]
+macro_rules! stream_parts {
+    (with $($part:path),+ $(,)?) => {
+        &[$(|source: $crate::stream::Source<'_>, params: &$crate::Params| {
+            $part(source, params).map($crate::Answer::from)
+        }),+]
+    };
+    ($($part:path),+ $(,)?) => {
+        &[$(|source: $crate::stream::Source<'_>, _: &$crate::Params| {
+            $part(source).map($crate::Answer::from)
+        }),+]
+    };
+}
+
+/// Checks that every streaming part gives the same answer as the in-memory one, or fails too
+///
+/// A streaming part may find the answer before it reads a bad line that fails the in-memory part, so the input
+/// should be valid. The errors themselves may differ too.
+pub fn same_answers(
+    input: &str,
+    parts: &[Part],
+    stream_parts: &[StreamPart],
+    params: &Params,
+) -> Result<()> {
+    for (ix, (part, stream_part)) in parts.iter().zip(stream_parts).enumerate() {
+        let answer = part(input, params).ok();
+        let streamed = stream_part(Source::Text(input), params).ok();
+        if answer != streamed {
+            bail!(
+                "Part {} answered {:?} in memory, but {:?} streaming",
+                ix + 1,
+                answer,
+                streamed
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_lines_like_str_lines() -> Result<()> {
+        let text = "a\r\nb\n\nc";
+        let lines = Source::Text(text)
+            .lines()?
+            .map(|l| l.map(|(_, line)| line))
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(lines, text.lines().collect::<Vec<_>>());
+        Ok(())
+    }
+
+    #[test]
+    fn numbers_the_lines() -> Result<()> {
+        let numbers = Source::Text("x\ny\n")
+            .lines()?
+            .map(|l| l.map(|(number, _)| number))
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(numbers, [1, 2]);
+        Ok(())
+    }
+
+    #[test]
+    fn reports_invalid_utf8() {
+        let path = std::env::temp_dir().join("aoc-common-stream-invalid-utf8.txt");
+        std::fs::write(&path, b"ok\n\xff\n").unwrap();
+        let lines = Source::File(&path).lines().unwrap().collect::<Vec<_>>();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(lines[0].is_ok());
+        assert!(lines[1]
+            .as_ref()
+            .unwrap_err()
+            .to_string()
+            .contains("line 2"));
+    }
+}
@@ -0,0 +1,59 @@
+//! The debug output of the solvers, like the instructions of day 8 or the rounds of day 22
+//!
+//! The days emit [tracing](https://docs.rs/tracing) events, which go nowhere until [`init`] installs a subscriber.
+//! Without one, each event is a single check of a static, so the solutions don't get slower.
+
+use anyhow::{anyhow, Result};
+use std::env;
+use std::io;
+use tracing_subscriber::EnvFilter;
+
+/// The variable with the filter of the events, like `AOC_TRACE=aoc08=trace` or `AOC_TRACE=debug`
+pub const ENV_VAR: &str = "AOC_TRACE";
+
+/// Prints the events to stderr if [`ENV_VAR`] is set, or every event if `all` is set
+///
+/// The filter of [`ENV_VAR`] wins over `all`, so it can narrow down `--trace` to one day.
+pub fn init(all: bool) -> Result<()> {
+    let filter = match filter(env::var(ENV_VAR).ok().as_deref(), all)? {
+        Some(filter) => filter,
+        None => return Ok(()),
+    };
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(io::stderr)
+        .without_time()
+        .try_init()
+        .map_err(|e| anyhow!(e))
+}
+
+/// The filter of the `directives` from [`ENV_VAR`], or of every event if `all` is set, or none
+fn filter(directives: Option<&str>, all: bool) -> Result<Option<EnvFilter>> {
+    match directives {
+        Some(directives) => EnvFilter::try_new(directives)
+            .map(Some)
+            .map_err(|e| anyhow!("Invalid {} filter: {}", ENV_VAR, e)),
+        None if all => Ok(Some(EnvFilter::new("trace"))),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_a_bad_filter() {
+        let error = filter(Some("aoc08=["), false).unwrap_err();
+        assert!(error.to_string().contains(ENV_VAR));
+    }
+
+    #[test]
+    fn filters_every_event_only_with_all() -> Result<()> {
+        assert!(filter(None, false)?.is_none());
+        assert!(filter(None, true)?.is_some());
+        assert!(filter(Some("aoc08=trace"), false)?.is_some());
+        Ok(())
+    }
+}
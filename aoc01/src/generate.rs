@@ -0,0 +1,80 @@
+//! Expense reports with exactly one pair and one triple of entries summing to the year
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use itertools::Itertools;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+
+/// The number of entries in the real input
+pub const PUZZLE_SCALE: usize = 200;
+
+/// The numbers of entries
+pub const SCALES: RangeInclusive<usize> = 5..=8000;
+
+const YEAR: u32 = 2020;
+
+/// Makes `scale` entries, at least 5 and at most 8000
+///
+/// The pair and the triple are planted among the fillers bigger than half of the year.
+/// Two fillers always sum to more than the year, and the fillers that complete another pair or triple are skipped.
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    let scale = scale.clamp(*SCALES.start(), *SCALES.end());
+
+    let planted = loop {
+        let a = rng.gen_range(1..YEAR / 2);
+        let c = rng.gen_range(1..YEAR - 1);
+        let d = rng.gen_range(1..YEAR - c);
+        let planted = [a, YEAR - a, c, d, YEAR - c - d];
+
+        let distinct = planted.iter().collect::<HashSet<_>>().len() == planted.len();
+        if distinct && sum_count(&planted, 2) == 1 && sum_count(&planted, 3) == 1 {
+            break planted;
+        }
+    };
+
+    let forbidden = planted
+        .iter()
+        .map(|&p| YEAR - p)
+        .chain(
+            planted
+                .iter()
+                .tuple_combinations()
+                .filter(|&(p, q)| p + q < YEAR)
+                .map(|(p, q)| YEAR - p - q),
+        )
+        .collect::<HashSet<_>>();
+
+    let mut entries = planted.iter().copied().collect::<HashSet<_>>();
+    while entries.len() < scale {
+        let filler = rng.gen_range(YEAR / 2 + 1..10_000);
+        if !forbidden.contains(&filler) {
+            entries.insert(filler);
+        }
+    }
+
+    let mut entries = entries.into_iter().collect::<Vec<_>>();
+    // The set has its own random order, which doesn't depend on the seed
+    entries.sort_unstable();
+    entries.shuffle(&mut rng);
+
+    Generated {
+        input: entries.iter().map(|e| format!("{}\n", e)).collect(),
+        answers: vec![
+            Some(Answer::from(planted[0] * planted[1])),
+            Some(Answer::from(planted[2] * planted[3] * planted[4])),
+        ],
+        params: Params::default(),
+    }
+}
+
+fn sum_count(numbers: &[u32], entry_count: usize) -> usize {
+    numbers
+        .iter()
+        .combinations(entry_count)
+        .filter(|c| c.iter().copied().sum::<u32>() == YEAR)
+        .count()
+}
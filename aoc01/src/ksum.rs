@@ -0,0 +1,280 @@
+//! The k-sum problem: `k` entries of the report with the given sum
+//!
+//! The entries are sorted once. Two entries are found with two pointers coming from both ends, and every entry on
+//! top of them fixes the smallest one and looks for the rest among the bigger ones, so `k` entries take
+//! `O(n^(k-1))` steps instead of the `O(n^k)` of trying every combination.
+//!
+//! That is quick for a pair or a triple of a long report, but each entry on top costs another factor of `n`. So four
+//! and more entries meet in the middle instead: the sums of all the pairs are sorted once, and the last two entries
+//! are looked up there for every first two, `O(n^(k-2) log n)` steps in all. The pairs take 8 bytes each, so we only
+//! keep up to [`MAX_PAIRS`] of them, for a report of about 4000 entries. A longer one falls back to fixing the
+//! entries one by one, and four entries out of a hundred thousand may take up to `n^3 / 6`, about 10^14 steps, when
+//! the smallest ones don't sum to the target early.
+//!
+//! The sums are `i128`, so any `k` entries of `i64` add up without overflow for all the `k` we can wait for.
+
+use std::cmp::Ordering;
+
+/// The most pairs we keep the sums of, 64 MiB of them
+pub const MAX_PAIRS: usize = 1 << 23;
+
+/// `k` entries at different positions of the report with the sum `target`, smallest first
+///
+/// The same value may be taken as many times as the report has it.
+pub fn find(entries: &[i64], k: usize, target: i64) -> Option<Vec<i64>> {
+    let mut sorted = entries.to_vec();
+    sorted.sort_unstable();
+
+    let mut picked = Vec::with_capacity(k);
+    let pair_count = sorted.len().saturating_mul(sorted.len().saturating_sub(1)) / 2;
+    let found = match k >= 4 && pair_count <= MAX_PAIRS {
+        true => PairSums::new(&sorted).find(0, k, i128::from(target), &mut picked),
+        false => find_in(&sorted, k, i128::from(target), &mut picked),
+    };
+    match found {
+        true => Some(picked),
+        false => None,
+    }
+}
+
+fn find_in(sorted: &[i64], k: usize, target: i128, picked: &mut Vec<i64>) -> bool {
+    match k {
+        0 => target == 0,
+        1 => match sorted.binary_search_by(|&entry| i128::from(entry).cmp(&target)) {
+            Ok(ix) => {
+                picked.push(sorted[ix]);
+                true
+            }
+            Err(_) => false,
+        },
+        2 => match find_pair(sorted, target) {
+            Some((a, b)) => {
+                picked.push(a);
+                picked.push(b);
+                true
+            }
+            None => false,
+        },
+        _ => {
+            for (ix, &entry) in sorted.iter().enumerate() {
+                // The rest are at least as big as this one, so the sum only grows from here
+                if i128::from(entry) * k as i128 > target {
+                    break;
+                }
+                // The same value had the same rest to choose from, and more of it
+                if ix > 0 && sorted[ix - 1] == entry {
+                    continue;
+                }
+
+                picked.push(entry);
+                if find_in(&sorted[ix + 1..], k - 1, target - i128::from(entry), picked) {
+                    return true;
+                }
+                picked.pop();
+            }
+            false
+        }
+    }
+}
+
+/// Every two entries of the sorted report, ordered by their sum and then by their positions
+struct PairSums<'a> {
+    sorted: &'a [i64],
+    pairs: Vec<(u32, u32)>,
+}
+
+impl<'a> PairSums<'a> {
+    fn new(sorted: &'a [i64]) -> Self {
+        // We may cast here, because the caller keeps the report short enough for MAX_PAIRS
+        let len = sorted.len() as u32;
+        let mut pair_sums = PairSums {
+            sorted,
+            pairs: (0..len)
+                .flat_map(|c| (c + 1..len).map(move |d| (c, d)))
+                .collect(),
+        };
+
+        let mut pairs = std::mem::take(&mut pair_sums.pairs);
+        pairs.sort_unstable_by_key(|&pair| (pair_sums.sum(pair), pair));
+        pair_sums.pairs = pairs;
+        pair_sums
+    }
+
+    fn sum(&self, (c, d): (u32, u32)) -> i128 {
+        i128::from(self.sorted[c as usize]) + i128::from(self.sorted[d as usize])
+    }
+
+    /// The same as `find_in` for the entries from `start` on, with the pairs for the last two
+    ///
+    /// The first two of four entries come in the same order as there, and the last two are the first pair after
+    /// them, so the entries are the same as well.
+    fn find(&self, start: usize, k: usize, target: i128, picked: &mut Vec<i64>) -> bool {
+        let sorted = self.sorted;
+        match k {
+            2 => self.find_last_pair(start, target, picked),
+            _ => {
+                for ix in start..sorted.len() {
+                    let entry = sorted[ix];
+                    if i128::from(entry) * k as i128 > target {
+                        break;
+                    }
+                    if ix > start && sorted[ix - 1] == entry {
+                        continue;
+                    }
+
+                    picked.push(entry);
+                    if self.find(ix + 1, k - 1, target - i128::from(entry), picked) {
+                        return true;
+                    }
+                    picked.pop();
+                }
+                false
+            }
+        }
+    }
+
+    /// The first pair with the sum `target` that starts at `start` or later
+    fn find_last_pair(&self, start: usize, target: i128, picked: &mut Vec<i64>) -> bool {
+        let from = self.pairs.partition_point(|&pair| self.sum(pair) < target);
+        let run = &self.pairs[from..];
+        let run = &run[..run.partition_point(|&pair| self.sum(pair) == target)];
+        // The run is ordered by the positions
+        let ix = run.partition_point(|&(c, _)| (c as usize) < start);
+
+        match run.get(ix) {
+            Some(&(c, d)) => {
+                picked.push(self.sorted[c as usize]);
+                picked.push(self.sorted[d as usize]);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Two entries with the sum `target`, moving the pointer of the smaller one up or of the bigger one down
+fn find_pair(sorted: &[i64], target: i128) -> Option<(i64, i64)> {
+    if sorted.len() < 2 {
+        return None;
+    }
+    let (mut lo, mut hi) = (0, sorted.len() - 1);
+
+    while lo < hi {
+        let sum = i128::from(sorted[lo]) + i128::from(sorted[hi]);
+        match sum.cmp(&target) {
+            Ordering::Less => lo += 1,
+            Ordering::Equal => return Some((sorted[lo], sorted[hi])),
+            Ordering::Greater => hi -= 1,
+        }
+    }
+
+    None
+}
+
+/// The positions of every `k` entries with the sum `target`, each set in ascending order
+///
+/// Two copies of a value are two entries, so they make two sets with a third entry, and a set with both of them.
+/// The sets are sorted, so the same report always gives the same list.
+pub fn all(entries: &[i64], k: usize, target: i64) -> Vec<Vec<usize>> {
+    let mut sorted = entries.iter().copied().enumerate().collect::<Vec<_>>();
+    sorted.sort_unstable_by_key(|&(_, entry)| entry);
+
+    let mut sets = vec![];
+    all_in(&sorted, k, i128::from(target), &mut vec![], &mut sets);
+
+    for set in sets.iter_mut() {
+        set.sort_unstable();
+    }
+    sets.sort_unstable();
+    sets
+}
+
+fn all_in(
+    sorted: &[(usize, i64)],
+    k: usize,
+    target: i128,
+    picked: &mut Vec<usize>,
+    sets: &mut Vec<Vec<usize>>,
+) {
+    match k {
+        0 if target == 0 => sets.push(picked.clone()),
+        0 => {}
+        1 => {
+            for &(ix, _) in sorted.iter().filter(|&&(_, e)| i128::from(e) == target) {
+                picked.push(ix);
+                sets.push(picked.clone());
+                picked.pop();
+            }
+        }
+        2 => {
+            for (a, b) in all_pairs(sorted, target) {
+                picked.push(a);
+                picked.push(b);
+                sets.push(picked.clone());
+                picked.truncate(picked.len() - 2);
+            }
+        }
+        _ => {
+            for (pos, &(ix, entry)) in sorted.iter().enumerate() {
+                if i128::from(entry) * k as i128 > target {
+                    break;
+                }
+
+                picked.push(ix);
+                all_in(
+                    &sorted[pos + 1..],
+                    k - 1,
+                    target - i128::from(entry),
+                    picked,
+                    sets,
+                );
+                picked.pop();
+            }
+        }
+    }
+}
+
+/// Every two entries with the sum `target`, with the two pointers skipping over the runs of equal values
+fn all_pairs(sorted: &[(usize, i64)], target: i128) -> Vec<(usize, usize)> {
+    let mut pairs = vec![];
+    if sorted.len() < 2 {
+        return pairs;
+    }
+    let (mut lo, mut hi) = (0, sorted.len() - 1);
+
+    while lo < hi {
+        let (low, high) = (sorted[lo].1, sorted[hi].1);
+        match (i128::from(low) + i128::from(high)).cmp(&target) {
+            Ordering::Less => lo += 1,
+            Ordering::Greater => hi -= 1,
+            // Every two of the run pair up
+            Ordering::Equal if low == high => {
+                for a in lo..=hi {
+                    for b in a + 1..=hi {
+                        pairs.push((sorted[a].0, sorted[b].0));
+                    }
+                }
+                break;
+            }
+            Ordering::Equal => {
+                let low_end = lo + sorted[lo..].iter().take_while(|p| p.1 == low).count();
+                let high_start = hi + 1
+                    - sorted[..=hi]
+                        .iter()
+                        .rev()
+                        .take_while(|p| p.1 == high)
+                        .count();
+                for a in lo..low_end {
+                    for b in high_start..=hi {
+                        pairs.push((sorted[a].0, sorted[b].0));
+                    }
+                }
+                lo = low_end;
+                // The run of the low value can't reach the run of the high value here, so hi stays above lo
+                hi = high_start - 1;
+            }
+        }
+    }
+
+    pairs
+}
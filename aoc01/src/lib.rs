@@ -0,0 +1,381 @@
+pub mod generate;
+pub mod ksum;
+pub mod stream;
+
+use anyhow::{anyhow, bail, Result};
+use aoc_common::parse::{parse_lines, parse_token};
+use aoc_common::{Answer, Param, Params};
+use std::fmt::Write;
+
+const TARGET: Param = Param {
+    name: "target",
+    default: "2020",
+    help: "The sum of the entries to find",
+};
+
+const ALL: Param = Param {
+    name: "all",
+    default: "false",
+    help:
+        "List every set of entries with the sum in a table, instead of the product of the first one",
+};
+
+const SHOW: Param = Param {
+    name: "show",
+    default: "false",
+    help: "Show the entries with their product, e.g. `299 * 1721 = 514579`, instead of the product alone",
+};
+
+const PART1_ENTRIES: Param = Param {
+    name: "part1-entries",
+    default: "2",
+    help: "The number of entries with the sum in part 1",
+};
+
+const PART2_ENTRIES: Param = Param {
+    name: "part2-entries",
+    default: "3",
+    help: "The number of entries with the sum in part 2",
+};
+
+pub const PARAMS: &[Param] = &[TARGET, ALL, SHOW, PART1_ENTRIES, PART2_ENTRIES];
+
+pub fn part1(input: &str) -> Result<i64> {
+    product(&find_entries(input, 2, Params::default().get(&TARGET)?)?)
+}
+
+pub fn part2(input: &str) -> Result<i64> {
+    product(&find_entries(input, 3, Params::default().get(&TARGET)?)?)
+}
+
+pub fn part1_with(input: &str, params: &Params) -> Result<Answer> {
+    solve(input, entry_count(params, &PART1_ENTRIES)?, params)
+}
+
+pub fn part2_with(input: &str, params: &Params) -> Result<Answer> {
+    solve(input, entry_count(params, &PART2_ENTRIES)?, params)
+}
+
+pub fn parse(input: &str) -> Result<impl Sized> {
+    get_data(input)
+}
+
+/// The entries with the sum `target`, smallest first
+pub fn find_entries(input: &str, entry_count: usize, target: i64) -> Result<Vec<i64>> {
+    ksum::find(&get_data(input)?, entry_count, target)
+        .ok_or_else(|| anyhow!("No {} entries sum to {}", entry_count, target))
+}
+
+fn entry_count(params: &Params, param: &Param) -> Result<usize> {
+    match params.get(param)? {
+        0 => bail!("The sum needs at least one entry"),
+        entry_count => Ok(entry_count),
+    }
+}
+
+fn solve(input: &str, entry_count: usize, params: &Params) -> Result<Answer> {
+    let target = params.get(&TARGET)?;
+    match params.get(&ALL)? {
+        true => table(input, entry_count, target).map(Answer::from),
+        false => answer(&find_entries(input, entry_count, target)?, params),
+    }
+}
+
+/// The product of the entries, after the entries themselves with `--show`
+fn answer(entries: &[i64], params: &Params) -> Result<Answer> {
+    let product = product(entries)?;
+    match params.get(&SHOW)? {
+        true => {
+            let entries = entries.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+            Ok(Answer::from(format!(
+                "{} = {}",
+                entries.join(" * "),
+                product
+            )))
+        }
+        false => Ok(Answer::from(product)),
+    }
+}
+
+fn product(entries: &[i64]) -> Result<i64> {
+    entries
+        .iter()
+        .try_fold(1_i64, |product, &entry| product.checked_mul(entry))
+        .ok_or_else(|| anyhow!("The product of the entries is too big"))
+}
+
+/// Every set of `entry_count` entries with the sum `target`, one row with the 0-based indices, the values and
+/// the product each
+pub fn table(input: &str, entry_count: usize, target: i64) -> Result<String> {
+    let entries = get_data(input)?;
+    let sets = ksum::all(&entries, entry_count, target);
+    if sets.is_empty() {
+        return Ok(format!("No {} entries sum to {}", entry_count, target));
+    }
+
+    let rows = sets
+        .iter()
+        .map(|set| {
+            // i128 is enough for three entries, and the product of more entries may be too big for it
+            let product = set
+                .iter()
+                .try_fold(1_i128, |product, &ix| {
+                    product.checked_mul(i128::from(entries[ix]))
+                })
+                .map_or_else(|| "too big".to_string(), |p| p.to_string());
+            vec![
+                join(set.iter().map(|ix| ix.to_string())),
+                join(set.iter().map(|&ix| entries[ix].to_string())),
+                product,
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let header = vec![
+        "Indices".to_string(),
+        "Values".to_string(),
+        "Product".to_string(),
+    ];
+    let mut widths = vec![0; header.len()];
+    for row in std::iter::once(&header).chain(rows.iter()) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut table = String::new();
+    for row in std::iter::once(&header).chain(rows.iter()) {
+        // We may .unwrap() here, because writing to a String never fails
+        writeln!(
+            table,
+            "{:<w0$}  {:<w1$}  {:>w2$}",
+            row[0],
+            row[1],
+            row[2],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2]
+        )
+        .unwrap();
+    }
+
+    // The runner prints the answer on its own line
+    table.pop();
+    Ok(table)
+}
+
+fn join(cells: impl Iterator<Item = String>) -> String {
+    cells.collect::<Vec<_>>().join(", ")
+}
+
+fn get_data(input: &str) -> Result<Vec<i64>> {
+    Ok(parse_lines(input, |l| parse_token(l, l, "an entry"))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::stream::{same_answers, Source};
+    use rand::Rng;
+
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(EXAMPLE)?, 514579);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(EXAMPLE)?, 241861950);
+        Ok(())
+    }
+
+    #[test]
+    fn another_target() -> Result<()> {
+        let params = Params::default().with(&TARGET, 1345);
+        assert_eq!(part1_with(EXAMPLE, &params)?, Answer::from(979_i64 * 366));
+        Ok(())
+    }
+
+    #[test]
+    fn finds_any_number_of_entries() -> Result<()> {
+        assert_eq!(find_entries(EXAMPLE, 2, 2020)?, [299, 1721]);
+        assert_eq!(find_entries(EXAMPLE, 3, 2020)?, [366, 675, 979]);
+        assert_eq!(
+            find_entries(EXAMPLE, 4, 1721 + 1456 + 675 + 366)?,
+            [366, 675, 1456, 1721]
+        );
+        assert_eq!(find_entries(EXAMPLE, 1, 979)?, [979]);
+        assert!(find_entries(EXAMPLE, 6, 100_000).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn meets_in_the_middle_with_the_same_entries() {
+        let mut rng = aoc_common::generate::rng(0);
+        for _ in 0..200 {
+            let entries = (0..rng.gen_range(4..40))
+                .map(|_| rng.gen_range(-20..60))
+                .collect::<Vec<i64>>();
+            let k = rng.gen_range(4..7);
+            let target = rng.gen_range(0..150);
+
+            // The smallest entries first, and then the smallest of the rest
+            let expected = ksum::all(&entries, k, target)
+                .iter()
+                .map(|set| {
+                    let mut values = set.iter().map(|&ix| entries[ix]).collect::<Vec<_>>();
+                    values.sort_unstable();
+                    values
+                })
+                .min();
+            assert_eq!(ksum::find(&entries, k, target), expected, "{:?}", entries);
+        }
+    }
+
+    #[test]
+    fn finds_four_big_entries_out_of_a_thousand() -> Result<()> {
+        // Only the four biggest entries sum to the target, so fixing three of them would take 10^8 steps
+        let input = (1..=1000_i64)
+            .map(|n| (n * 5).to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let params = Params::default()
+            .with(&PART1_ENTRIES, 4)
+            .with(&TARGET, 5 * (997 + 998 + 999 + 1000))
+            .with(&SHOW, true);
+        assert_eq!(
+            part1_with(&input, &params)?.to_string(),
+            "4985 * 4990 * 4995 * 5000 = 621256871250000"
+        );
+
+        let params = params.with(&PART1_ENTRIES, 0);
+        assert!(part1_with(&input, &params).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn points_at_a_bad_entry() {
+        let parts = aoc_common::parts![with part1_with, part2_with];
+        let stream_parts = aoc_common::stream_parts![with stream::part1_with, stream::part2_with];
+        let input = "1721\n979\n36x\n";
+        let errors = [
+            parts[0](input, &Params::default()).err().unwrap(),
+            stream_parts[0](Source::Text(input), &Params::default())
+                .err()
+                .unwrap(),
+        ];
+        for error in errors {
+            let error = error.downcast_ref::<aoc_common::ParseError>().unwrap();
+            assert_eq!((error.line, error.column), (Some(3), 1));
+        }
+    }
+
+    #[test]
+    fn takes_a_value_as_many_times_as_it_is_there() -> Result<()> {
+        assert_eq!(find_entries("1010\n1010", 2, 2020)?, [1010, 1010]);
+        assert!(find_entries("1010\n3", 2, 2020).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn handles_a_long_report() -> Result<()> {
+        // Only the ones are small enough
+        let input = (1..=100_000_u32)
+            .map(|n| (n * 3).to_string())
+            .chain(vec!["1".to_string(), "1".to_string()])
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(find_entries(&input, 2, 2)?, [1, 1]);
+        assert_eq!(find_entries(&input, 3, 5)?, [1, 1, 3]);
+        assert_eq!(find_entries(&input, 4, 299_999 + 300_000 + 2)?.len(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn lists_the_copies_as_different_entries() {
+        assert_eq!(
+            ksum::all(&[1010, 5, 1010, 1010], 2, 2020),
+            [[0, 2], [0, 3], [2, 3]]
+        );
+        assert_eq!(
+            ksum::all(&[1, 2, 1, 2, 3], 2, 3),
+            [[0, 1], [0, 3], [1, 2], [2, 3]]
+        );
+        assert_eq!(ksum::all(&[1, 1, 1, 1], 3, 3).len(), 4);
+        assert!(ksum::all(&[1, 2], 3, 3).is_empty());
+    }
+
+    #[test]
+    fn takes_the_negative_and_big_entries() -> Result<()> {
+        let input = "-5\n2025\n9000000000\n-8999997980\n7";
+        assert_eq!(find_entries(input, 2, 2020)?, [-8999997980, 9000000000]);
+        assert_eq!(ksum::all(&get_data(input)?, 2, 2020), [[0, 1], [2, 3]]);
+        // The product of the first pair doesn't fit into i64
+        assert!(part1(input).is_err());
+
+        let params = Params::default().with(&TARGET, 2027);
+        let input = "-5\n2025\n100\n7";
+        assert_eq!(part2_with(input, &params)?, Answer::from(-5_i64 * 7 * 2025));
+        Ok(())
+    }
+
+    #[test]
+    fn shows_the_entries() -> Result<()> {
+        let params = Params::default().with(&SHOW, true);
+        assert_eq!(
+            part1_with(EXAMPLE, &params)?.to_string(),
+            "299 * 1721 = 514579"
+        );
+        assert_eq!(
+            part2_with(EXAMPLE, &params)?.to_string(),
+            "366 * 675 * 979 = 241861950"
+        );
+
+        let params = params.with(&TARGET, -5 + 1000 + 1010);
+        let input = "-5\n2025\n1000\n1010";
+        assert_eq!(
+            part2_with(input, &params)?.to_string(),
+            "-5 * 1000 * 1010 = -5050000"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn prints_the_table() -> Result<()> {
+        let params = Params::default().with(&ALL, true);
+        let expected = "\
+Indices  Values     Product
+0, 3     1721, 299   514579";
+        assert_eq!(part1_with(EXAMPLE, &params)?.to_string(), expected);
+
+        let params = params.with(&TARGET, 1);
+        assert_eq!(
+            part2_with(EXAMPLE, &params)?.to_string(),
+            "No 3 entries sum to 1"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn streams_the_same_answers() -> Result<()> {
+        let parts = aoc_common::parts![with part1_with, part2_with];
+        let stream_parts = aoc_common::stream_parts![with stream::part1_with, stream::part2_with];
+        let params = Params::default();
+        same_answers(EXAMPLE, parts, stream_parts, &params)?;
+        for seed in 0..10 {
+            let input = generate::generate(seed, 300).input;
+            same_answers(&input, parts, stream_parts, &params)?;
+        }
+        // The negative entries make the stream read the report twice
+        same_answers("-10\n2030\n5\n1000\n1015", parts, stream_parts, &params)?;
+        same_answers(EXAMPLE, parts, stream_parts, &params.with(&SHOW, true))?;
+        let params = Params::default()
+            .with(&PART1_ENTRIES, 4)
+            .with(&PART2_ENTRIES, 1);
+        same_answers("1000\n5\n1000\n15\n2020\n-5", parts, stream_parts, &params)?;
+        Ok(())
+    }
+}
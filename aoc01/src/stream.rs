@@ -0,0 +1,83 @@
+//! The same parts, reading the entries line by line
+//!
+//! Only the entries up to the target minus the smallest entries can be in the sum, so we count them and forget the
+//! rest of the report. Without the negative entries that's everything up to the target, and the report is read
+//! only once. A negative entry raises the limit, and then we read the report again with the new limit, which
+//! stdin can't do.
+
+use crate::{answer, entry_count, ksum, product, ALL, PART1_ENTRIES, PART2_ENTRIES, TARGET};
+use anyhow::{anyhow, bail, Result};
+use aoc_common::parse::parse_token;
+use aoc_common::stream::Source;
+use aoc_common::{Answer, Params};
+use std::collections::BTreeMap;
+
+pub fn part1(source: Source<'_>) -> Result<i64> {
+    product(&process(source, 2, &Params::default())?)
+}
+
+pub fn part2(source: Source<'_>) -> Result<i64> {
+    product(&process(source, 3, &Params::default())?)
+}
+
+pub fn part1_with(source: Source<'_>, params: &Params) -> Result<Answer> {
+    let entry_count = entry_count(params, &PART1_ENTRIES)?;
+    answer(&process(source, entry_count, params)?, params)
+}
+
+pub fn part2_with(source: Source<'_>, params: &Params) -> Result<Answer> {
+    let entry_count = entry_count(params, &PART2_ENTRIES)?;
+    answer(&process(source, entry_count, params)?, params)
+}
+
+/// The entries with the sum, smallest first
+fn process(source: Source<'_>, entry_count: usize, params: &Params) -> Result<Vec<i64>> {
+    if params.get(&ALL)? {
+        bail!(
+            "Every set of entries can only be listed in memory, because it needs all the indices"
+        );
+    }
+    let target = params.get::<i64>(&TARGET)?;
+
+    let (mut counts, min) = count(source, entry_count, i128::from(target))?;
+    if min < 0 {
+        if !source.rereadable() {
+            bail!("The negative entries need a second pass over the report, so it must be a file, not stdin");
+        }
+        // The other entries are at least the smallest one, so the entry is at most the target minus them
+        let limit = i128::from(target) - i128::from(min) * (entry_count as i128 - 1);
+        counts = count(source, entry_count, limit)?.0;
+    }
+
+    // The copies of an entry are entries of their own there, so the entries come out the same as in memory
+    let entries = counts
+        .iter()
+        .flat_map(|(&entry, &count)| std::iter::repeat_n(entry, count))
+        .collect::<Vec<_>>();
+    ksum::find(&entries, entry_count, target)
+        .ok_or_else(|| anyhow!("No {} entries sum to {}", entry_count, target))
+}
+
+/// The counts of the entries up to the `limit`, and the smallest entry of the report
+///
+/// We never need more copies of one entry than there are entries in the sum.
+fn count(
+    source: Source<'_>,
+    entry_count: usize,
+    limit: i128,
+) -> Result<(BTreeMap<i64, usize>, i64)> {
+    let mut counts = BTreeMap::new();
+    let mut min = 0;
+
+    for line in source.lines()? {
+        let (number, line) = line?;
+        let entry: i64 = parse_token(&line, &line, "an entry").map_err(|e| e.at_line(number))?;
+        min = min.min(entry);
+        if i128::from(entry) <= limit {
+            let count = counts.entry(entry).or_insert(0);
+            *count = entry_count.min(*count + 1);
+        }
+    }
+
+    Ok((counts, min))
+}
@@ -0,0 +1,78 @@
+//! Password lists where each line is valid or invalid by construction
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::Rng;
+use std::ops::RangeInclusive;
+
+/// The number of lines in the real input
+pub const PUZZLE_SCALE: usize = 1000;
+
+/// The numbers of lines
+pub const SCALES: RangeInclusive<usize> = 0..=usize::MAX;
+
+/// Makes `scale` lines
+///
+/// The letters at the two positions are chosen first, so we know the validity of the line for both policies.
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    let mut input = String::new();
+    let mut valid_by_count = 0;
+    let mut valid_by_position = 0;
+
+    for _ in 0..scale {
+        let length = rng.gen_range(3..=20);
+        let min = rng.gen_range(1..length);
+        let max = rng.gen_range(min + 1..=length);
+        let char = random_letter(&mut rng);
+
+        let mut password = (1..=length)
+            .map(|_| match rng.gen_bool(0.3) {
+                true => char,
+                false => other_letter(&mut rng, char),
+            })
+            .collect::<Vec<_>>();
+
+        let at_min = rng.gen_bool(0.5);
+        let at_max = rng.gen_bool(0.5);
+        for &(position, here) in &[(min, at_min), (max, at_max)] {
+            password[position - 1] = match here {
+                true => char,
+                false => other_letter(&mut rng, char),
+            };
+        }
+
+        let count = password.iter().filter(|&&c| c == char).count();
+        if (min..=max).contains(&count) {
+            valid_by_count += 1;
+        }
+        if at_min ^ at_max {
+            valid_by_position += 1;
+        }
+
+        let password = password.into_iter().collect::<String>();
+        input.push_str(&format!("{}-{} {}: {}\n", min, max, char, password));
+    }
+
+    Generated {
+        input,
+        answers: vec![
+            Some(Answer::from(valid_by_count)),
+            Some(Answer::from(valid_by_position)),
+        ],
+        params: Params::default(),
+    }
+}
+
+fn random_letter(rng: &mut impl Rng) -> char {
+    char::from(rng.gen_range(b'a'..=b'z'))
+}
+
+fn other_letter(rng: &mut impl Rng, except: char) -> char {
+    loop {
+        let letter = random_letter(rng);
+        if letter != except {
+            return letter;
+        }
+    }
+}
@@ -0,0 +1,332 @@
+#[macro_use]
+extern crate lazy_static;
+
+pub mod generate;
+pub mod policy;
+pub mod report;
+pub mod stream;
+pub mod unit;
+
+use anyhow::{Context, Result};
+use aoc_common::parse::{parse_lines, parse_token};
+use aoc_common::{Answer, Param, Params, ParseError};
+use policy::Policy;
+use regex::Regex;
+use std::str::FromStr;
+use unit::Unit;
+
+const PART1_POLICY: Param = Param {
+    name: "part1-policy",
+    default: "count",
+    help: "The policy of the first part, e.g. \"count & contains=digit\"",
+};
+
+const PART2_POLICY: Param = Param {
+    name: "part2-policy",
+    default: "positions",
+    help: "The policy of the second part, e.g. \"positions | max-repeats=2\"",
+};
+
+const REPORT: Param = Param {
+    name: "report",
+    default: "false",
+    help: "Report the verdict of every line and why, instead of the number of the valid passwords",
+};
+
+const UNIT: Param = Param {
+    name: "unit",
+    default: "chars",
+    help: "What the positions and the counts count: bytes, chars or graphemes",
+};
+
+pub const PARAMS: &[Param] = &[PART1_POLICY, PART2_POLICY, REPORT, UNIT];
+
+pub fn part1(input: &str) -> Result<usize> {
+    let policy = get_policy(&Params::default(), &PART1_POLICY)?;
+    process(input, Unit::Chars, &*policy)
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    let policy = get_policy(&Params::default(), &PART2_POLICY)?;
+    process(input, Unit::Chars, &*policy)
+}
+
+pub fn part1_with(input: &str, params: &Params) -> Result<Answer> {
+    solve(input, params, &PART1_POLICY)
+}
+
+pub fn part2_with(input: &str, params: &Params) -> Result<Answer> {
+    solve(input, params, &PART2_POLICY)
+}
+
+fn solve(input: &str, params: &Params, policy: &Param) -> Result<Answer> {
+    let policy = get_policy(params, policy)?;
+    let unit = params.get(&UNIT)?;
+    match params.get(&REPORT)? {
+        true => Ok(Answer::from(report::report(input, unit, &*policy))),
+        false => process(input, unit, &*policy).map(Answer::from),
+    }
+}
+
+pub fn parse(input: &str) -> Result<impl Sized> {
+    get_entries(input, Unit::Chars)
+}
+
+/// The number of the passwords that follow the policy, counting their chars in `unit`
+pub fn process(input: &str, unit: Unit, policy: &dyn Policy) -> Result<usize> {
+    let entries = get_entries(input, unit)?;
+    Ok(entries.iter().filter(|e| policy.allows(e)).count())
+}
+
+fn get_policy(params: &Params, param: &Param) -> Result<Box<dyn Policy>> {
+    let text = params.get::<String>(param)?;
+    policy::parse(&text).with_context(|| format!("Bad --{}", param.name))
+}
+
+fn get_entries(input: &str, unit: Unit) -> Result<Vec<Entry>> {
+    Ok(parse_lines(input, |line| Entry::parse(line, unit))?)
+}
+
+/// One line of the password database
+pub struct Entry {
+    pub min: usize,
+    pub max: usize,
+    /// Exactly one unit
+    pub letter: String,
+    pub password: String,
+    /// What the positions and the counts of the policies count
+    pub unit: Unit,
+}
+
+impl Entry {
+    /// Parses the line with the letter of one `unit`
+    ///
+    /// The password is everything after the first `": "` behind the letter, of any length and with any chars.
+    pub fn parse(line: &str, unit: Unit) -> Result<Self, ParseError> {
+        lazy_static! {
+            // We may .unwrap() here, because this regex is correct
+            static ref RE: Regex = Regex::new(r"^([0-9]+)-([0-9]+) (.+?): (.*)$").unwrap();
+        }
+
+        let caps = RE
+            .captures(line)
+            .ok_or_else(|| ParseError::new(line, line, "an entry like \"1-3 a: abcde\""))?;
+
+        // We may .unwrap() the groups, because they always participate in the match
+        let token = |n| caps.get(n).unwrap().as_str();
+
+        let letter = token(3);
+        if unit.split(letter).count() != 1 {
+            return Err(ParseError::new(
+                line,
+                letter,
+                format!("one {}", unit.name()),
+            ));
+        }
+
+        Ok(Entry {
+            min: parse_token(line, token(1), "a number")?,
+            max: parse_token(line, token(2), "a number")?,
+            letter: letter.to_string(),
+            password: token(4).to_string(),
+            unit,
+        })
+    }
+
+    /// The units of the password
+    pub fn units(&self) -> impl Iterator<Item = &[u8]> {
+        self.unit.split(&self.password)
+    }
+}
+
+/// Parses the line counting the chars
+impl FromStr for Entry {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        Entry::parse(line, Unit::Chars)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::stream::same_answers;
+    use aoc_common::Part;
+
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(EXAMPLE)?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(EXAMPLE)?, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn parses_an_entry() -> Result<()> {
+        let entry = Entry::from_str("2-9 c: ccccccccc")?;
+        assert_eq!((entry.min, entry.max, entry.letter.as_str()), (2, 9, "c"));
+        assert_eq!(entry.password, "ccccccccc");
+        Ok(())
+    }
+
+    #[test]
+    fn counts_in_any_unit() -> Result<()> {
+        let answer = |part: Part, input, unit| -> Result<String> {
+            Ok(part(input, &Params::default().with(&UNIT, unit))?.to_string())
+        };
+
+        // The decomposed é is two chars, but one grapheme
+        let input = "2-2 e\u{301}: e\u{301}xe\u{301}\n";
+        assert_eq!(answer(part1_with, input, "graphemes")?, "1");
+        let error = part1_with(input, &Params::default()).err().unwrap();
+        assert_eq!(
+            error.downcast_ref::<ParseError>().map(|e| e.message()),
+            Some("line 1, column 5: expected one char, found \"e\\u{301}\"".to_string())
+        );
+
+        // ä is two bytes, so a is the third byte and only the second char
+        let input = "2-3 a: \u{e4}a\n";
+        assert_eq!(answer(part2_with, input, "bytes")?, "1");
+        assert_eq!(answer(part2_with, input, "chars")?, "0");
+
+        // Long passwords count to the end, and the password is everything after the first ": "
+        let input = format!("1-1 z: {}z\n2-2 :: a: b:\n", "a".repeat(30));
+        assert_eq!(answer(part1_with, &input, "chars")?, "2");
+        Ok(())
+    }
+
+    #[test]
+    fn reports_the_line_of_a_bad_entry() {
+        let error = parse_lines("1-3 a: abcde\n1-3 b cdefg\n", Entry::from_str)
+            .err()
+            .unwrap();
+        assert_eq!(error.line, Some(2));
+        assert_eq!(error.column, 1);
+    }
+
+    #[test]
+    fn combines_the_policies() -> Result<()> {
+        let count = |text| process(EXAMPLE, Unit::Chars, &*policy::parse(text)?);
+        assert_eq!(count("count | positions")?, 2);
+        assert_eq!(count("count & positions")?, 1);
+        assert_eq!(count("!count")?, 1);
+        assert_eq!(count("max-repeats=1")?, 2);
+        assert_eq!(count("!(count & positions) & contains=lower")?, 2);
+        assert_eq!(count("contains=digit | contains=upper")?, 0);
+
+        let params = Params::default().with(&PART2_POLICY, "max-repeats=9");
+        assert_eq!(part2_with(EXAMPLE, &params)?.to_string(), "3");
+        Ok(())
+    }
+
+    #[test]
+    fn reports_every_line() -> Result<()> {
+        let input = "1-3 a: abcde\n1-3 b: cdefg\n1-3 c cdefg\n2-9 c: ccccccccc\n";
+        let params = Params::default().with(&REPORT, true);
+        let expected = "\
+Line 1: valid
+Line 2: invalid, found 0 'b', allowed 1-3
+Line 3: malformed, column 1: expected an entry like \"1-3 a: abcde\", found \"1-3 c cdefg\"
+Line 4: valid
+2 valid, 1 invalid, 1 malformed";
+        assert_eq!(part1_with(input, &params)?.to_string(), expected);
+
+        let reasons = |text| -> Result<Vec<String>> {
+            let policy = policy::parse(text)?;
+            Ok(parse_lines(EXAMPLE, Entry::from_str)?
+                .iter()
+                .filter(|e| !policy.allows(e))
+                .map(|e| policy.violation(e))
+                .collect())
+        };
+        assert_eq!(
+            reasons("positions")?,
+            [
+                "'b' at neither position 1 nor 3",
+                "'c' at both positions 2 and 9"
+            ]
+        );
+        assert_eq!(
+            reasons("max-repeats=2 & !contains=lower")?[2],
+            "'c' 9 times in a row, allowed 2"
+        );
+        assert_eq!(
+            reasons("!contains=lower | contains=digit")?[0],
+            "follows contains=lower and no digit"
+        );
+
+        let entry = Entry::from_str("1-14 a: abc")?;
+        assert_eq!(
+            policy::OnePosition.violation(&entry),
+            "position 14 out of range"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn points_at_a_bad_policy() {
+        let column = |text| policy::parse(text).err().unwrap().column;
+        assert_eq!(column("count &"), 8);
+        assert_eq!(column("count & size"), 9);
+        assert_eq!(column("contains=vowel"), 10);
+        assert_eq!(column("max-repeats"), 12);
+        assert_eq!(column("(count | positions"), 19);
+        assert_eq!(column("count positions"), 7);
+    }
+
+    #[test]
+    fn limits_the_nesting_of_a_policy() -> Result<()> {
+        let nested = |depth| format!("{}count{}", "!(".repeat(depth), ")".repeat(depth));
+        let deepest = nested(policy::MAX_DEPTH / 2);
+        assert_eq!(
+            process(EXAMPLE, Unit::Chars, &*policy::parse(&deepest)?)?,
+            2
+        );
+
+        let error = policy::parse(&nested(policy::MAX_DEPTH / 2 + 1))
+            .err()
+            .unwrap();
+        assert_eq!(error.column, policy::MAX_DEPTH + 1);
+        assert_eq!(
+            error.expected,
+            format!("at most {} nested ! and (", policy::MAX_DEPTH)
+        );
+
+        // Far more than the stack would take without the limit
+        let error = policy::parse(&"!".repeat(100_000)).err().unwrap();
+        assert_eq!(error.column, policy::MAX_DEPTH + 1);
+        Ok(())
+    }
+
+    #[test]
+    fn streams_the_same_answers() -> Result<()> {
+        let parts = aoc_common::parts![with part1_with, part2_with];
+        let stream_parts = aoc_common::stream_parts![with stream::part1_with, stream::part2_with];
+        let params = Params::default();
+        same_answers(EXAMPLE, parts, stream_parts, &params)?;
+        let params = params.with(&PART1_POLICY, "count & max-repeats=2");
+        same_answers(EXAMPLE, parts, stream_parts, &params)?;
+        let params = params.with(&REPORT, true);
+        same_answers("1-3 a: abcde\n1-3 b cdefg\n", parts, stream_parts, &params)?;
+        let params = Params::default().with(&UNIT, "graphemes");
+        same_answers(
+            "1-2 e\u{301}: e\u{301}\u{e4}\n",
+            parts,
+            stream_parts,
+            &params,
+        )?;
+        let params = Params::default();
+        for seed in 0..10 {
+            let input = generate::generate(seed, 300).input;
+            same_answers(&input, parts, stream_parts, &params)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,418 @@
+//! The password policies and the little language that combines them
+//!
+//! A policy is anything that can tell whether the password of an [`Entry`] follows it. The language names the
+//! policies and combines them, e.g. `count & !contains=digit | positions`:
+//!
+//! - `count`: the letter of the entry occurs from min to max times, the policy of the first part
+//! - `positions`: the letter of the entry is at exactly one of the 1-based positions min and max, the policy of the
+//!   second part
+//! - `max-repeats=N`: no unit occurs more than `N` times in a row
+//! - `contains=CLASS`: some char is `lower`, `upper`, `digit`, `letter` or `symbol`
+//! - `!a`: the password doesn't follow `a`
+//! - `a & b`: it follows both, `&` goes before `|`
+//! - `a | b`: it follows one of them or both
+//! - `(a)`: the parentheses group the policies as usual, nested at most [`MAX_DEPTH`] deep together with `!`
+//!
+//! The counts and the positions are in the [`Unit`](crate::unit::Unit) of the entry, bytes, chars or graphemes,
+//! while the classes are always about the chars.
+
+use crate::unit::show;
+use crate::Entry;
+use aoc_common::parse::parse_token;
+use aoc_common::ParseError;
+
+pub trait Policy {
+    fn allows(&self, entry: &Entry) -> bool;
+
+    /// Why the password of the entry doesn't follow the policy, asked only about the entries it doesn't allow
+    fn violation(&self, _entry: &Entry) -> String {
+        "doesn't follow the policy".to_string()
+    }
+}
+
+/// The letter of the entry occurs from min to max times
+pub struct CharCount;
+
+impl Policy for CharCount {
+    fn allows(&self, entry: &Entry) -> bool {
+        (entry.min..=entry.max).contains(&char_count(entry))
+    }
+
+    fn violation(&self, entry: &Entry) -> String {
+        format!(
+            "found {} {}, allowed {}-{}",
+            char_count(entry),
+            show(entry.letter.as_bytes()),
+            entry.min,
+            entry.max
+        )
+    }
+}
+
+fn char_count(entry: &Entry) -> usize {
+    let letter = entry.letter.as_bytes();
+    entry.units().filter(|&unit| unit == letter).count()
+}
+
+/// The letter of the entry is at exactly one of the 1-based positions min and max
+///
+/// Both positions must be in the password.
+pub struct OnePosition;
+
+impl Policy for OnePosition {
+    fn allows(&self, entry: &Entry) -> bool {
+        let letter = entry.letter.as_bytes();
+        match (unit_at(entry, entry.min), unit_at(entry, entry.max)) {
+            // Two equal positions can't be exactly one of them
+            (Some(first), Some(second)) if entry.min != entry.max => {
+                (first == letter) != (second == letter)
+            }
+            _ => false,
+        }
+    }
+
+    fn violation(&self, entry: &Entry) -> String {
+        let letter = entry.letter.as_bytes();
+        match (unit_at(entry, entry.min), unit_at(entry, entry.max)) {
+            (None, _) => format!("position {} out of range", entry.min),
+            (_, None) => format!("position {} out of range", entry.max),
+            _ if entry.min == entry.max => format!("both positions are {}", entry.min),
+            (Some(first), _) if first == letter => format!(
+                "{} at both positions {} and {}",
+                show(letter),
+                entry.min,
+                entry.max
+            ),
+            _ => format!(
+                "{} at neither position {} nor {}",
+                show(letter),
+                entry.min,
+                entry.max
+            ),
+        }
+    }
+}
+
+/// The unit at the 1-based `position`, if the password is that long
+fn unit_at(entry: &Entry, position: usize) -> Option<&[u8]> {
+    position.checked_sub(1).and_then(|ix| entry.units().nth(ix))
+}
+
+/// No unit occurs more than the given number of times in a row
+pub struct MaxRepeats(pub usize);
+
+impl Policy for MaxRepeats {
+    fn allows(&self, entry: &Entry) -> bool {
+        let mut previous = None;
+        let mut repeats = 0;
+
+        for unit in entry.units() {
+            repeats = match previous == Some(unit) {
+                true => repeats + 1,
+                false => 1,
+            };
+            if repeats > self.0 {
+                return false;
+            }
+            previous = Some(unit);
+        }
+
+        true
+    }
+
+    fn violation(&self, entry: &Entry) -> String {
+        let mut longest: Option<(&[u8], usize)> = None;
+        let mut units = entry.units().peekable();
+
+        while let Some(unit) = units.next() {
+            let mut repeats = 1;
+            while units.next_if_eq(&unit).is_some() {
+                repeats += 1;
+            }
+            if repeats > longest.map_or(0, |(_, most)| most) {
+                longest = Some((unit, repeats));
+            }
+        }
+
+        match longest {
+            Some((unit, repeats)) => format!(
+                "{} {} times in a row, allowed {}",
+                show(unit),
+                repeats,
+                self.0
+            ),
+            None => format!("no {}s, allowed {} in a row", entry.unit.name(), self.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Class {
+    Lower,
+    Upper,
+    Digit,
+    Letter,
+    /// Anything but the letters, the digits and the whitespace
+    Symbol,
+}
+
+impl Class {
+    fn contains(self, c: char) -> bool {
+        match self {
+            Class::Lower => c.is_lowercase(),
+            Class::Upper => c.is_uppercase(),
+            Class::Digit => c.is_numeric(),
+            Class::Letter => c.is_alphabetic(),
+            Class::Symbol => !c.is_alphanumeric() && !c.is_whitespace(),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Class::Lower => "lowercase letter",
+            Class::Upper => "uppercase letter",
+            Class::Digit => "digit",
+            Class::Letter => "letter",
+            Class::Symbol => "symbol",
+        }
+    }
+}
+
+/// Some char of the password is in the class
+pub struct Contains(pub Class);
+
+impl Policy for Contains {
+    fn allows(&self, entry: &Entry) -> bool {
+        entry.password.chars().any(|c| self.0.contains(c))
+    }
+
+    fn violation(&self, _entry: &Entry) -> String {
+        format!("no {}", self.0.name())
+    }
+}
+
+pub struct Not {
+    pub policy: Box<dyn Policy>,
+    /// How the policy was written, for the violations
+    pub text: String,
+}
+
+impl Policy for Not {
+    fn allows(&self, entry: &Entry) -> bool {
+        !self.policy.allows(entry)
+    }
+
+    fn violation(&self, _entry: &Entry) -> String {
+        format!("follows {}", self.text)
+    }
+}
+
+pub struct All(pub Vec<Box<dyn Policy>>);
+
+impl Policy for All {
+    fn allows(&self, entry: &Entry) -> bool {
+        self.0.iter().all(|p| p.allows(entry))
+    }
+
+    /// The violation of the first policy it doesn't follow
+    fn violation(&self, entry: &Entry) -> String {
+        self.0
+            .iter()
+            .find(|p| !p.allows(entry))
+            .map(|p| p.violation(entry))
+            .unwrap_or_default()
+    }
+}
+
+pub struct Any(pub Vec<Box<dyn Policy>>);
+
+impl Policy for Any {
+    fn allows(&self, entry: &Entry) -> bool {
+        self.0.iter().any(|p| p.allows(entry))
+    }
+
+    /// The violations of all the policies, because it doesn't follow any of them
+    fn violation(&self, entry: &Entry) -> String {
+        self.0
+            .iter()
+            .map(|p| p.violation(entry))
+            .collect::<Vec<_>>()
+            .join(" and ")
+    }
+}
+
+/// How many `!` and `(` may be open at once, so that the parser and the policies don't overflow the stack
+pub const MAX_DEPTH: usize = 100;
+
+/// Parses the policy language, the columns of the errors point into `text`
+pub fn parse(text: &str) -> Result<Box<dyn Policy>, ParseError> {
+    let mut parser = Parser {
+        text,
+        pos: 0,
+        depth: 0,
+    };
+    let policy = parser.any()?;
+
+    parser.skip_whitespace();
+    match parser.rest().is_empty() {
+        true => Ok(policy),
+        false => Err(ParseError::new(text, parser.rest(), "&, | or the end")),
+    }
+}
+
+/// A recursive descent parser, one method per level of precedence
+struct Parser<'a> {
+    text: &'a str,
+    /// In bytes
+    pos: usize,
+    /// The number of `!` and `(` around the position
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skips `c` and the whitespace before it, if `c` is next
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        match self.rest().starts_with(c) {
+            true => {
+                self.pos += c.len_utf8();
+                true
+            }
+            false => false,
+        }
+    }
+
+    /// The longest piece of the rest made of the chars of the names
+    fn word(&mut self) -> &'a str {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+            .unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn any(&mut self) -> Result<Box<dyn Policy>, ParseError> {
+        let mut policies = vec![self.all()?];
+        while self.eat('|') {
+            policies.push(self.all()?);
+        }
+        Ok(match policies.len() {
+            // We may .unwrap() here, because we have just checked the length
+            1 => policies.pop().unwrap(),
+            _ => Box::new(Any(policies)),
+        })
+    }
+
+    fn all(&mut self) -> Result<Box<dyn Policy>, ParseError> {
+        let mut policies = vec![self.not()?];
+        while self.eat('&') {
+            policies.push(self.not()?);
+        }
+        Ok(match policies.len() {
+            // We may .unwrap() here, because we have just checked the length
+            1 => policies.pop().unwrap(),
+            _ => Box::new(All(policies)),
+        })
+    }
+
+    /// Goes one level deeper after the `!` or `(` that was just eaten
+    fn nest(&mut self) -> Result<(), ParseError> {
+        self.depth += 1;
+        match self.depth > MAX_DEPTH {
+            // We may use the index here, because both chars are one byte long
+            true => Err(ParseError::new(
+                self.text,
+                &self.text[self.pos - 1..self.pos],
+                format!("at most {} nested ! and (", MAX_DEPTH),
+            )),
+            false => Ok(()),
+        }
+    }
+
+    fn not(&mut self) -> Result<Box<dyn Policy>, ParseError> {
+        if self.eat('!') {
+            self.nest()?;
+            self.skip_whitespace();
+            let start = self.pos;
+            let policy = self.not()?;
+            self.depth -= 1;
+            let text = self.text[start..self.pos].to_string();
+            return Ok(Box::new(Not { policy, text }));
+        }
+        if self.eat('(') {
+            self.nest()?;
+            let policy = self.any()?;
+            self.depth -= 1;
+            return match self.eat(')') {
+                true => Ok(policy),
+                false => Err(ParseError::new(self.text, self.rest(), ")")),
+            };
+        }
+        self.policy()
+    }
+
+    fn policy(&mut self) -> Result<Box<dyn Policy>, ParseError> {
+        self.skip_whitespace();
+        let name = self.word();
+
+        match name {
+            "count" => Ok(Box::new(CharCount)),
+            "positions" => Ok(Box::new(OnePosition)),
+            "max-repeats" => {
+                let value = self.value(name)?;
+                let repeats = parse_token(self.text, value, "a number")?;
+                Ok(Box::new(MaxRepeats(repeats)))
+            }
+            "contains" => {
+                let value = self.value(name)?;
+                let class = match value {
+                    "lower" => Class::Lower,
+                    "upper" => Class::Upper,
+                    "digit" => Class::Digit,
+                    "letter" => Class::Letter,
+                    "symbol" => Class::Symbol,
+                    _ => {
+                        return Err(ParseError::new(
+                            self.text,
+                            value,
+                            "lower, upper, digit, letter or symbol",
+                        ))
+                    }
+                };
+                Ok(Box::new(Contains(class)))
+            }
+            // An empty name points at whatever is there instead
+            "" => {
+                let rest = self.rest();
+                let found = &rest[..rest.chars().next().map_or(0, char::len_utf8)];
+                Err(ParseError::new(self.text, found, "a policy"))
+            }
+            _ => Err(ParseError::new(
+                self.text,
+                name,
+                "count, positions, max-repeats or contains",
+            )),
+        }
+    }
+
+    /// The value after `=` of the policy `name`
+    fn value(&mut self, name: &'a str) -> Result<&'a str, ParseError> {
+        match self.rest().starts_with('=') {
+            true => {
+                self.pos += 1;
+                Ok(self.word())
+            }
+            false => Err(ParseError::after(self.text, name, "=")),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! The verdict of every line of the database, for the audits
+//!
+//! The parts stop at the first malformed line, while the report goes on and tells for each line whether it's valid,
+//! invalid or malformed, and why:
+//!
+//! ```text
+//! Line 1: valid
+//! Line 2: invalid, found 0 'b', allowed 1-3
+//! Line 3: malformed, column 1: expected an entry like "1-3 a: abcde", found "1-3 c cdefg"
+//! 1 valid, 1 invalid, 1 malformed
+//! ```
+
+use crate::policy::Policy;
+use crate::unit::Unit;
+use crate::Entry;
+
+pub struct Report<'a> {
+    unit: Unit,
+    policy: &'a dyn Policy,
+    lines: String,
+    valid: usize,
+    invalid: usize,
+    malformed: usize,
+}
+
+impl<'a> Report<'a> {
+    pub fn new(unit: Unit, policy: &'a dyn Policy) -> Self {
+        Report {
+            unit,
+            policy,
+            lines: String::new(),
+            valid: 0,
+            invalid: 0,
+            malformed: 0,
+        }
+    }
+
+    /// Adds the verdict of the line `number`
+    pub fn add(&mut self, number: usize, line: &str) {
+        let policy = self.policy;
+        let verdict = match Entry::parse(line, self.unit) {
+            Ok(entry) if policy.allows(&entry) => {
+                self.valid += 1;
+                "valid".to_string()
+            }
+            Ok(entry) => {
+                self.invalid += 1;
+                format!("invalid, {}", policy.violation(&entry))
+            }
+            Err(e) => {
+                self.malformed += 1;
+                format!("malformed, {}", e.message())
+            }
+        };
+        self.lines += &format!("Line {}: {}\n", number, verdict);
+    }
+
+    /// The verdicts of the lines and the counts of the verdicts
+    pub fn finish(self) -> String {
+        format!(
+            "{}{} valid, {} invalid, {} malformed",
+            self.lines, self.valid, self.invalid, self.malformed
+        )
+    }
+}
+
+pub fn report(input: &str, unit: Unit, policy: &dyn Policy) -> String {
+    let mut report = Report::new(unit, policy);
+    for (ix, line) in input.lines().enumerate() {
+        report.add(ix + 1, line);
+    }
+    report.finish()
+}
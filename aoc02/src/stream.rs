@@ -0,0 +1,58 @@
+//! The same parts, reading the passwords line by line
+
+use crate::policy::Policy;
+use crate::report::Report;
+use crate::unit::Unit;
+use crate::{get_policy, Entry, PART1_POLICY, PART2_POLICY, REPORT, UNIT};
+use anyhow::Result;
+use aoc_common::stream::Source;
+use aoc_common::{Answer, Param, Params};
+
+pub fn part1(source: Source<'_>) -> Result<usize> {
+    let policy = get_policy(&Params::default(), &PART1_POLICY)?;
+    process(source, Unit::Chars, &*policy)
+}
+
+pub fn part2(source: Source<'_>) -> Result<usize> {
+    let policy = get_policy(&Params::default(), &PART2_POLICY)?;
+    process(source, Unit::Chars, &*policy)
+}
+
+pub fn part1_with(source: Source<'_>, params: &Params) -> Result<Answer> {
+    solve(source, params, &PART1_POLICY)
+}
+
+pub fn part2_with(source: Source<'_>, params: &Params) -> Result<Answer> {
+    solve(source, params, &PART2_POLICY)
+}
+
+fn solve(source: Source<'_>, params: &Params, policy: &Param) -> Result<Answer> {
+    let policy = get_policy(params, policy)?;
+    let unit = params.get(&UNIT)?;
+    match params.get(&REPORT)? {
+        true => report(source, unit, &*policy).map(Answer::from),
+        false => process(source, unit, &*policy).map(Answer::from),
+    }
+}
+
+fn process(source: Source<'_>, unit: Unit, policy: &dyn Policy) -> Result<usize> {
+    let mut count = 0;
+    for line in source.lines()? {
+        let (number, line) = line?;
+        let entry = Entry::parse(&line, unit).map_err(|e| e.at_line(number))?;
+        if policy.allows(&entry) {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// The report keeps only its own lines, so it's as big as the output and not as the database
+fn report(source: Source<'_>, unit: Unit, policy: &dyn Policy) -> Result<String> {
+    let mut report = Report::new(unit, policy);
+    for line in source.lines()? {
+        let (number, line) = line?;
+        report.add(number, &line);
+    }
+    Ok(report.finish())
+}
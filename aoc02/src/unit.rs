@@ -0,0 +1,64 @@
+//! What the positions and the counts of the policies count
+//!
+//! The puzzle's passwords are ASCII, where all the units agree. Elsewhere `é` may be two bytes, one char, or two
+//! chars `e` and `\u{301}` that make one grapheme, and then the policies only see the letter the way the user
+//! typed it if they count the graphemes.
+
+use aoc_common::ParseError;
+use std::str::FromStr;
+use unicode_segmentation::UnicodeSegmentation;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Bytes,
+    Chars,
+    /// The extended grapheme clusters of Unicode, what the user sees as one char
+    Graphemes,
+}
+
+impl Unit {
+    /// The units of `text` in order, as their bytes so that all the units compare the same way
+    pub fn split(self, text: &str) -> Box<dyn Iterator<Item = &[u8]> + '_> {
+        match self {
+            Unit::Bytes => Box::new(text.as_bytes().chunks(1)),
+            Unit::Chars => Box::new(
+                text.char_indices()
+                    .map(move |(ix, c)| &text.as_bytes()[ix..ix + c.len_utf8()]),
+            ),
+            Unit::Graphemes => Box::new(text.graphemes(true).map(str::as_bytes)),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Unit::Bytes => "byte",
+            Unit::Chars => "char",
+            Unit::Graphemes => "grapheme",
+        }
+    }
+}
+
+impl FromStr for Unit {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bytes" => Ok(Unit::Bytes),
+            "chars" => Ok(Unit::Chars),
+            "graphemes" => Ok(Unit::Graphemes),
+            _ => Err(ParseError::new(s, s, "bytes, chars or graphemes")),
+        }
+    }
+}
+
+/// The unit quoted the way the violations show it, with the bytes that aren't text in hex
+pub fn show(unit: &[u8]) -> String {
+    match std::str::from_utf8(unit) {
+        Ok(text) => format!("'{}'", text),
+        Err(_) => unit
+            .iter()
+            .map(|byte| format!("0x{:02x}", byte))
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
@@ -0,0 +1,54 @@
+//! Maps of trees of any height
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::Rng;
+use std::ops::RangeInclusive;
+
+/// The number of rows in the real input
+pub const PUZZLE_SCALE: usize = 323;
+
+/// The numbers of rows
+pub const SCALES: RangeInclusive<usize> = 1..=usize::MAX;
+
+const WIDTH: usize = 31;
+
+/// Makes a map of `scale` rows, at least one, as wide as the real one
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    let rows = (0..scale.max(*SCALES.start()))
+        .map(|_| (0..WIDTH).map(|_| rng.gen_bool(0.25)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    // Walks down the slope one step at a time, wrapping around the right edge
+    let trees = |right: usize, down: usize| {
+        let (mut row, mut column, mut trees) = (0, 0, 0);
+        while row < rows.len() {
+            trees += usize::from(rows[row][column]);
+            row += down;
+            column = (column + right) % WIDTH;
+        }
+        trees
+    };
+
+    let part2 = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)]
+        .iter()
+        .map(|&(right, down)| trees(right, down))
+        .product::<usize>();
+
+    Generated {
+        input: rows
+            .iter()
+            .map(|r| {
+                let mut row = r
+                    .iter()
+                    .map(|&t| if t { '#' } else { '.' })
+                    .collect::<String>();
+                row.push('\n');
+                row
+            })
+            .collect(),
+        answers: vec![Some(Answer::from(trees(3, 1))), Some(Answer::from(part2))],
+        params: Params::default(),
+    }
+}
@@ -0,0 +1,87 @@
+pub mod generate;
+
+use anyhow::Result;
+use aoc_common::grid::Grid;
+
+pub fn part1(input: &str) -> Result<usize> {
+    process(input, &[Slope::new(3, 1)])
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    process(
+        input,
+        &[
+            Slope::new(1, 1),
+            Slope::new(3, 1),
+            Slope::new(5, 1),
+            Slope::new(7, 1),
+            Slope::new(1, 2),
+        ],
+    )
+}
+
+pub fn parse(input: &str) -> Result<impl Sized> {
+    get_data(input)
+}
+
+struct Slope {
+    right: usize,
+    down: usize,
+}
+
+impl Slope {
+    fn new(right: usize, down: usize) -> Self {
+        Slope { right, down }
+    }
+}
+
+fn process(input: &str, slopes: &[Slope]) -> Result<usize> {
+    let map = get_data(input)?;
+
+    let result = slopes
+        .iter()
+        .map(|slope| {
+            (0..map.height())
+                .step_by(slope.down)
+                .map(|row| (row, row * slope.right / slope.down))
+                // The map repeats to the right
+                .filter(|&pos| map.get_wrapping(pos) == Some(&true))
+                .count()
+        })
+        .product::<usize>();
+
+    Ok(result)
+}
+
+/// `true` for the trees
+fn get_data(input: &str) -> Result<Grid<bool>> {
+    let map = Grid::parse(
+        input,
+        |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        },
+        "a tree or an open square",
+    )?;
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(EXAMPLE)?, 7);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(EXAMPLE)?, 336);
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+//! Batches of passports that are missing a field, have an invalid field or are fully valid
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::ops::RangeInclusive;
+
+/// The number of passports in the real input
+pub const PUZZLE_SCALE: usize = 290;
+
+/// The numbers of passports
+pub const SCALES: RangeInclusive<usize> = 0..=usize::MAX;
+
+const REQUIRED: [&str; 7] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+const EYE_COLORS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+/// Makes `scale` passports, a third of each kind on average
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    let mut passports = vec![];
+    let mut complete = 0;
+    let mut valid = 0;
+
+    for _ in 0..scale {
+        let mut fields = REQUIRED
+            .iter()
+            .map(|&key| (key, valid_value(&mut rng, key)))
+            .collect::<Vec<_>>();
+        // The country is optional, but it must be a number when it's there
+        if rng.gen_bool(0.5) {
+            fields.push(("cid", rng.gen_range(100..350).to_string()));
+        }
+
+        match rng.gen_range(0..3) {
+            0 => {
+                let missing = rng.gen_range(0..REQUIRED.len());
+                fields.remove(missing);
+            }
+            1 => {
+                complete += 1;
+                let broken = rng.gen_range(0..REQUIRED.len());
+                fields[broken].1 = invalid_value(&mut rng, REQUIRED[broken]);
+            }
+            _ => {
+                complete += 1;
+                valid += 1;
+            }
+        }
+
+        fields.shuffle(&mut rng);
+        let mut passport = String::new();
+        for (ix, (key, value)) in fields.iter().enumerate() {
+            if ix > 0 {
+                passport.push(if rng.gen_bool(0.3) { '\n' } else { ' ' });
+            }
+            passport.push_str(&format!("{}:{}", key, value));
+        }
+        passports.push(passport);
+    }
+
+    Generated {
+        input: passports
+            .iter()
+            .map(|p| format!("{}\n", p))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        answers: vec![Some(Answer::from(complete)), Some(Answer::from(valid))],
+        params: Params::default(),
+    }
+}
+
+fn valid_value(rng: &mut impl Rng, key: &str) -> String {
+    match key {
+        "byr" => rng.gen_range(1920..=2002).to_string(),
+        "iyr" => rng.gen_range(2010..=2020).to_string(),
+        "eyr" => rng.gen_range(2020..=2030).to_string(),
+        "hgt" => match rng.gen_bool(0.5) {
+            true => format!("{}cm", rng.gen_range(150..=193)),
+            false => format!("{}in", rng.gen_range(59..=76)),
+        },
+        "hcl" => format!("#{:06x}", rng.gen_range(0..=0xff_ffff)),
+        // We may .unwrap() here, because the list isn't empty
+        "ecl" => EYE_COLORS.choose(rng).unwrap().to_string(),
+        _ => format!("{:09}", rng.gen_range(0..1_000_000_000)),
+    }
+}
+
+fn invalid_value(rng: &mut impl Rng, key: &str) -> String {
+    match key {
+        "byr" => rng.gen_range(2003..=2030).to_string(),
+        "iyr" => rng.gen_range(1990..2010).to_string(),
+        "eyr" => rng.gen_range(2031..=2040).to_string(),
+        "hgt" => match rng.gen_range(0..3) {
+            0 => format!("{}cm", rng.gen_range(194..=250)),
+            1 => format!("{}in", rng.gen_range(20..59)),
+            _ => rng.gen_range(150..=193).to_string(),
+        },
+        "hcl" => match rng.gen_bool(0.5) {
+            true => format!("{:06x}", rng.gen_range(0..=0xff_ffff)),
+            false => format!("#{:05x}z", rng.gen_range(0..=0xf_ffff)),
+        },
+        "ecl" => ["xry", "zzz", "blue", "#123abc"][rng.gen_range(0..4)].to_string(),
+        _ => match rng.gen_bool(0.5) {
+            true => format!("{:08}", rng.gen_range(0..100_000_000)),
+            false => format!("{:010}", rng.gen_range(0..1_000_000_000)),
+        },
+    }
+}
@@ -0,0 +1,377 @@
+pub mod generate;
+
+use anyhow::Result;
+use aoc_common::parse::{parse_token, split_numbered_groups};
+use aoc_common::ParseError;
+use std::convert::TryFrom;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+pub fn part1(input: &str) -> Result<usize> {
+    process(input, false)
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    process(input, true)
+}
+
+pub fn parse(input: &str) -> Result<impl Sized + '_> {
+    Ok(get_unverified_data(input))
+}
+
+fn process(input: &str, advanced_validation: bool) -> Result<usize> {
+    let unverified_passports = get_unverified_data(input);
+
+    Ok(match advanced_validation {
+        false => unverified_passports.len(),
+        true => unverified_passports
+            .into_iter()
+            .flat_map(Passport::try_from)
+            .count(),
+    })
+}
+
+/// The passports with all the required fields
+///
+/// A passport with a malformed field is as invalid as one without a required field, so it's skipped too.
+fn get_unverified_data(input: &str) -> Vec<PassportUnverified<'_>> {
+    split_numbered_groups(input)
+        .flat_map(|(_, group)| PassportUnverified::try_from(group))
+        .filter(|p| p.has_all_required_fields())
+        .collect()
+}
+
+#[derive(Default, Debug)]
+struct PassportUnverified<'a> {
+    /// The lines of the passport, for the errors
+    group: &'a str,
+    birth_year: Option<&'a str>,
+    issue_year: Option<&'a str>,
+    expiration_year: Option<&'a str>,
+    height: Option<&'a str>,
+    hair_color: Option<&'a str>,
+    eye_color: Option<&'a str>,
+    passport_id: Option<&'a str>,
+    country_id: Option<&'a str>,
+}
+
+// I use TryFrom instead of FromStr because of E0308
+impl<'a> TryFrom<&'a str> for PassportUnverified<'a> {
+    type Error = ParseError;
+
+    fn try_from(items: &'a str) -> Result<Self, Self::Error> {
+        let mut new_passport = PassportUnverified {
+            group: items,
+            ..PassportUnverified::default()
+        };
+
+        for key_value in items.split_ascii_whitespace() {
+            let (key, value) = match key_value.find(':') {
+                // We may use the indices here, because ':' is one byte long
+                Some(ix) => (&key_value[..ix], &key_value[ix + 1..]),
+                None => {
+                    return Err(ParseError::in_block(
+                        items,
+                        key_value,
+                        "a field like \"byr:1937\"",
+                    ))
+                }
+            };
+            if let Some(ix) = value.find(':') {
+                return Err(ParseError::in_block(
+                    items,
+                    &value[ix..],
+                    "one : in a field",
+                ));
+            }
+
+            match key {
+                "byr" => new_passport.birth_year = Some(value),
+                "iyr" => new_passport.issue_year = Some(value),
+                "eyr" => new_passport.expiration_year = Some(value),
+                "hgt" => new_passport.height = Some(value),
+                "hcl" => new_passport.hair_color = Some(value),
+                "ecl" => new_passport.eye_color = Some(value),
+                "pid" => new_passport.passport_id = Some(value),
+                "cid" => new_passport.country_id = Some(value),
+                _ => {}
+            }
+        }
+
+        Ok(new_passport)
+    }
+}
+
+impl PassportUnverified<'_> {
+    fn has_all_required_fields(&self) -> bool {
+        self.birth_year.is_some()
+            && self.issue_year.is_some()
+            && self.expiration_year.is_some()
+            && self.height.is_some()
+            && self.hair_color.is_some()
+            && self.eye_color.is_some()
+            && self.passport_id.is_some()
+    }
+}
+
+// I allow this here because we use this type for validation only
+#[allow(dead_code)]
+struct Passport {
+    birth_year: u16,
+    issue_year: u16,
+    expiration_year: u16,
+    height: Height,
+    hair_color: Color,
+    eye_color: EyeColor,
+    passport_id: u32,
+    country_id: Option<u32>,
+}
+
+// The values are never read for the same reason
+#[allow(dead_code)]
+enum Height {
+    Cm(u16),
+    In(u16),
+}
+
+impl FromStr for Height {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        const EXPECTED: &str = "a height like 150cm or 60in";
+
+        match value.len() {
+            4 | 5 => {
+                // We should add better UTF-8 support in production here
+                // But for now we are fine with using the index
+                let (number, unit) =
+                    match (value.get(..value.len() - 2), value.get(value.len() - 2..)) {
+                        (Some(number), Some(unit)) => (number, unit),
+                        _ => return Err(ParseError::new(value, value, EXPECTED)),
+                    };
+                let number = parse_token(value, number, "a number")?;
+
+                match unit {
+                    "cm" if (150..=193).contains(&number) => Ok(Height::Cm(number)),
+                    "in" if (59..=79).contains(&number) => Ok(Height::In(number)),
+                    "cm" | "in" => Err(ParseError::new(value, value, EXPECTED)),
+                    _ => Err(ParseError::new(value, unit, "cm or in")),
+                }
+            }
+            _ => Err(ParseError::new(value, value, EXPECTED)),
+        }
+    }
+}
+
+#[allow(dead_code)]
+struct Color(u32);
+
+impl FromStr for Color {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match (value.len(), value.starts_with('#')) {
+            // We have already checked the length and the first character, so we may use the index here
+            (7, true) => Ok(Color(
+                u32::from_str_radix(&value[1..7], 16)
+                    .map_err(|_| ParseError::new(value, &value[1..7], "six hex digits"))?,
+            )),
+            _ => Err(ParseError::new(value, value, "a color like #123abc")),
+        }
+    }
+}
+
+enum EyeColor {
+    Amb,
+    Blu,
+    Brn,
+    Gry,
+    Grn,
+    Hzl,
+    Oth,
+}
+
+impl FromStr for EyeColor {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "amb" => Ok(EyeColor::Amb),
+            "blu" => Ok(EyeColor::Blu),
+            "brn" => Ok(EyeColor::Brn),
+            "gry" => Ok(EyeColor::Gry),
+            "grn" => Ok(EyeColor::Grn),
+            "hzl" => Ok(EyeColor::Hzl),
+            "oth" => Ok(EyeColor::Oth),
+            _ => Err(ParseError::new(
+                value,
+                value,
+                "amb, blu, brn, gry, grn, hzl or oth",
+            )),
+        }
+    }
+}
+
+/// The passport with the valid values only, the errors point at the first invalid one
+impl TryFrom<PassportUnverified<'_>> for Passport {
+    type Error = ParseError;
+
+    fn try_from(u_passport: PassportUnverified) -> Result<Self, Self::Error> {
+        // Let's double check this thing
+        let group = u_passport.group;
+        if !u_passport.has_all_required_fields() {
+            return Err(ParseError::new(
+                group,
+                &group[group.len()..],
+                "all the required fields",
+            ));
+        }
+
+        // The values are parsed on their own, and the errors go to the lines of the passport
+        // We may use .unwrap()'s here, because we checked .has_all_required_fields() above
+        let birth_year = u_passport.birth_year.unwrap();
+        let birth_year = Passport::parse_range(birth_year, 1920..=2002)
+            .map_err(|e| e.within(group, birth_year))?;
+
+        let issue_year = u_passport.issue_year.unwrap();
+        let issue_year = Passport::parse_range(issue_year, 2010..=2020)
+            .map_err(|e| e.within(group, issue_year))?;
+
+        let expiration_year = u_passport.expiration_year.unwrap();
+        let expiration_year = Passport::parse_range(expiration_year, 2020..=2030)
+            .map_err(|e| e.within(group, expiration_year))?;
+
+        let height = u_passport.height.unwrap();
+        let height = Height::from_str(height).map_err(|e| e.within(group, height))?;
+
+        let hair_color = u_passport.hair_color.unwrap();
+        let hair_color = Color::from_str(hair_color).map_err(|e| e.within(group, hair_color))?;
+
+        let eye_color = u_passport.eye_color.unwrap();
+        let eye_color = EyeColor::from_str(eye_color).map_err(|e| e.within(group, eye_color))?;
+
+        let passport_id = u_passport.passport_id.unwrap();
+        let passport_id = Passport::parse_fixed_length(passport_id, 9)
+            .map_err(|e| e.within(group, passport_id))?;
+
+        let country_id = match u_passport.country_id {
+            Some(cid) => Some(parse_token(cid, cid, "a number").map_err(|e| e.within(group, cid))?),
+            None => None,
+        };
+
+        Ok(Passport {
+            birth_year,
+            issue_year,
+            expiration_year,
+            height,
+            hair_color,
+            eye_color,
+            passport_id,
+            country_id,
+        })
+    }
+}
+
+impl Passport {
+    fn parse_range(raw_value: &str, range: RangeInclusive<u16>) -> Result<u16, ParseError> {
+        let expected = format!("a year from {} to {}", range.start(), range.end());
+        match raw_value.parse::<u16>() {
+            Ok(value) if range.contains(&value) => Ok(value),
+            _ => Err(ParseError::new(raw_value, raw_value, expected)),
+        }
+    }
+
+    fn parse_fixed_length(raw_value: &str, length: usize) -> Result<u32, ParseError> {
+        let expected = format!("a number of {} digits", length);
+        match raw_value.parse::<u32>() {
+            Ok(value) if raw_value.len() == length => Ok(value),
+            _ => Err(ParseError::new(raw_value, raw_value, expected)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("../input/input.txt");
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+    const EXAMPLE_INVALID: &str = include_str!("../input/example_invalid.txt");
+    const EXAMPLE_VALID: &str = include_str!("../input/example_valid.txt");
+
+    #[test]
+    fn same_answers_for_lf_and_crlf() -> Result<()> {
+        let lf = INPUT.replace("\r\n", "\n");
+        let crlf = lf.replace('\n', "\r\n");
+
+        assert_eq!(part1(&lf)?, part1(&crlf)?);
+        assert_eq!(part2(&lf)?, part2(&crlf)?);
+        assert!(part1(&lf)? > 1);
+        Ok(())
+    }
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(EXAMPLE)?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_examples() -> Result<()> {
+        assert_eq!(part2(EXAMPLE_INVALID)?, 0);
+        assert_eq!(part2(EXAMPLE_VALID)?, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn validates_fields_like_the_examples() {
+        assert!(Passport::parse_range("2002", 1920..=2002).is_ok());
+        assert!(Passport::parse_range("2003", 1920..=2002).is_err());
+
+        assert!(Height::from_str("60in").is_ok());
+        assert!(Height::from_str("190cm").is_ok());
+        assert!(Height::from_str("190in").is_err());
+        assert!(Height::from_str("190").is_err());
+
+        assert!(Color::from_str("#123abc").is_ok());
+        assert!(Color::from_str("#123abz").is_err());
+        assert!(Color::from_str("123abc").is_err());
+
+        assert!(EyeColor::from_str("brn").is_ok());
+        assert!(EyeColor::from_str("wat").is_err());
+
+        assert!(Passport::parse_fixed_length("000000001", 9).is_ok());
+        assert!(Passport::parse_fixed_length("0123456789", 9).is_err());
+    }
+
+    #[test]
+    fn skips_the_passports_with_malformed_fields() {
+        let valid =
+            "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd\nbyr:1937 iyr:2017 cid:147 hgt:183cm";
+        let without_colon = valid.replace("cid:147", "cid");
+        let two_colons = valid.replace("byr:1937", "byr:1937:x");
+        let input = [valid, &without_colon, &two_colons].join("\n\n");
+        assert_eq!(part1(&input).ok(), Some(1));
+        assert_eq!(part2(&input).ok(), Some(1));
+
+        let error = PassportUnverified::try_from(without_colon.as_str())
+            .err()
+            .unwrap();
+        assert_eq!((error.line, error.column), (Some(2), 19));
+        let error = PassportUnverified::try_from(two_colons.as_str())
+            .err()
+            .unwrap();
+        assert_eq!((error.line, error.column), (Some(2), 9));
+        assert_eq!(error.expected, "one : in a field");
+    }
+
+    #[test]
+    fn points_at_the_bad_fields() -> Result<()> {
+        let input = "eyr:2029 byr:1980 iyr:2012 hcl:#623a2f\necl:grn pid:087499704 hgt:74xx";
+        let passport = get_unverified_data(input).pop().unwrap();
+        let error = Passport::try_from(passport).err().unwrap();
+        assert_eq!((error.line, error.column), (Some(2), 29));
+        assert_eq!(error.expected, "cm or in");
+        Ok(())
+    }
+}
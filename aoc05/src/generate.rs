@@ -0,0 +1,56 @@
+//! Boarding passes of a plane that is full except for our seat
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::convert::TryFrom;
+use std::ops::RangeInclusive;
+
+/// The number of boarding passes in the real input
+pub const PUZZLE_SCALE: usize = 800;
+
+/// The numbers of boarding passes
+pub const SCALES: RangeInclusive<usize> = 2..=1000;
+
+const SEATS: u32 = 1 << 10;
+
+/// Makes `scale` boarding passes, at least 2 and at most 1000
+///
+/// The passes are a run of seat IDs without one in the middle, which is our seat.
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    // We may .unwrap() here, because the clamped scale is small
+    let count = u32::try_from(scale.clamp(*SCALES.start(), *SCALES.end())).unwrap();
+
+    let first = rng.gen_range(1..SEATS - count);
+    let last = first + count;
+    let my_seat = rng.gen_range(first + 1..last);
+
+    let mut passes = (first..=last)
+        .filter(|&id| id != my_seat)
+        .map(boarding_pass)
+        .collect::<Vec<_>>();
+    passes.shuffle(&mut rng);
+
+    Generated {
+        input: passes.iter().map(|p| format!("{}\n", p)).collect(),
+        answers: vec![Some(Answer::from(last)), Some(Answer::from(my_seat))],
+        params: Params::default(),
+    }
+}
+
+fn boarding_pass(id: u32) -> String {
+    (0..10)
+        .rev()
+        .map(|bit| {
+            let set = id >> bit & 1 == 1;
+            match (bit >= 3, set) {
+                (true, false) => 'F',
+                (true, true) => 'B',
+                (false, false) => 'L',
+                (false, true) => 'R',
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,115 @@
+pub mod generate;
+pub mod stream;
+
+use anyhow::Result;
+use aoc_common::parse::{parse_lines, OrInvalid};
+use aoc_common::ParseError;
+use std::collections::HashSet;
+
+pub fn part1(input: &str) -> Result<u32> {
+    Ok(get_data(input)?.into_iter().max().or_invalid()?)
+}
+
+pub fn part2(input: &str) -> Result<u32> {
+    let all_passes: HashSet<_> = (0..(2_u32.pow(10))).collect();
+    let nearby_passes: HashSet<_> = get_data(input)?.into_iter().collect();
+    let nearby_passes_min = nearby_passes.iter().min().or_invalid()?;
+    let nearby_passes_max = nearby_passes.iter().max().or_invalid()?;
+    let my_seat = all_passes
+        .difference(&nearby_passes)
+        .find(|&x| x > nearby_passes_min && x < nearby_passes_max)
+        .or_invalid()?;
+
+    Ok(*my_seat)
+}
+
+pub fn parse(input: &str) -> Result<impl Sized> {
+    get_data(input)
+}
+
+fn get_data(input: &str) -> Result<Vec<u32>> {
+    Ok(parse_lines(input, get_id)?)
+}
+
+/// The seat ID is the row times 8 plus the column, so the 7 row letters and the 3 column letters are its 10 bits
+fn get_id(line: &str) -> Result<u32, ParseError> {
+    let mut id = 0;
+    let mut letters = line.char_indices();
+
+    for bit in (0..10).rev() {
+        let (zero, one, expected) = match bit >= 3 {
+            true => ('F', 'B', "F or B"),
+            false => ('L', 'R', "L or R"),
+        };
+        let (offset, letter) = letters
+            .next()
+            .ok_or_else(|| ParseError::after(line, line, expected))?;
+        // We may use the index here, because the offset is at a char boundary
+        let token = &line[offset..offset + letter.len_utf8()];
+        match letter {
+            l if l == zero => {}
+            l if l == one => id |= 1 << bit,
+            _ => return Err(ParseError::new(line, token, expected)),
+        }
+    }
+
+    match letters.next() {
+        Some((offset, _)) => Err(ParseError::new(
+            line,
+            &line[offset..],
+            "the end of the line",
+        )),
+        None => Ok(id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::stream::same_answers;
+    use aoc_common::Params;
+
+    #[test]
+    fn seat_ids_of_the_examples() -> Result<()> {
+        assert_eq!(get_id("FBFBBFFRLR")?, 357);
+        assert_eq!(get_id("BFFFBBFRRR")?, 567);
+        assert_eq!(get_id("FFFBBBFRRR")?, 119);
+        assert_eq!(get_id("BBFFBBFRLL")?, 820);
+        Ok(())
+    }
+
+    #[test]
+    fn points_at_the_bad_passes() {
+        let error = |input: &str| {
+            let error = part1(input).err().unwrap();
+            let error = error.downcast_ref::<ParseError>().unwrap().clone();
+            (error.line, error.column, error.expected)
+        };
+        let expected = |line, column, expected: &str| (Some(line), column, expected.to_string());
+
+        assert_eq!(error("FBFBBFFRLR\nFBFBBFLRLR\n"), expected(2, 7, "F or B"));
+        assert_eq!(error("FBFBBFFRL\n"), expected(1, 10, "L or R"));
+        assert_eq!(
+            error("FBFBBFFRLRR\n"),
+            expected(1, 11, "the end of the line")
+        );
+    }
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1("BFFFBBFRRR\nFFFBBBFRRR\nBBFFBBFRLL\n")?, 820);
+        Ok(())
+    }
+
+    #[test]
+    fn streams_the_same_answers() -> Result<()> {
+        let parts = aoc_common::parts![part1, part2];
+        let stream_parts = aoc_common::stream_parts![stream::part1, stream::part2];
+        let params = Params::default();
+        for seed in 0..10 {
+            let input = generate::generate(seed, 300).input;
+            same_answers(&input, parts, stream_parts, &params)?;
+        }
+        Ok(())
+    }
+}
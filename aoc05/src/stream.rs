@@ -0,0 +1,41 @@
+//! The same parts, reading the boarding passes line by line
+//!
+//! The seat IDs have 10 bits, so we only keep which of the 1024 seats are taken.
+
+use crate::get_id;
+use anyhow::Result;
+use aoc_common::parse::OrInvalid;
+use aoc_common::stream::Source;
+
+const SEATS: u32 = 1024;
+
+pub fn part1(source: Source<'_>) -> Result<u32> {
+    let taken = get_taken(source)?;
+    Ok((0..SEATS)
+        .rev()
+        .find(|&id| taken[id as usize])
+        .or_invalid()?)
+}
+
+pub fn part2(source: Source<'_>) -> Result<u32> {
+    let taken = get_taken(source)?;
+    let min = (0..SEATS).find(|&id| taken[id as usize]).or_invalid()?;
+    let max = (0..SEATS)
+        .rev()
+        .find(|&id| taken[id as usize])
+        .or_invalid()?;
+    let my_seat = (min + 1..max)
+        .find(|&id| !taken[id as usize])
+        .or_invalid()?;
+    Ok(my_seat)
+}
+
+fn get_taken(source: Source<'_>) -> Result<Vec<bool>> {
+    let mut taken = vec![false; SEATS as usize];
+    for line in source.lines()? {
+        let (number, line) = line?;
+        let id = get_id(&line).map_err(|e| e.at_line(number))?;
+        taken[id as usize] = true;
+    }
+    Ok(taken)
+}
@@ -0,0 +1,52 @@
+//! Customs declaration forms of groups of any size
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::Rng;
+use std::ops::RangeInclusive;
+
+/// The number of groups in the real input
+pub const PUZZLE_SCALE: usize = 490;
+
+/// The numbers of groups
+pub const SCALES: RangeInclusive<usize> = 0..=usize::MAX;
+
+/// Makes `scale` groups of 1 to 5 people
+///
+/// The answers of a person are a bit set of the 26 questions, so the counts are unions and intersections of the sets.
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    let mut groups = vec![];
+    let mut anyone = 0;
+    let mut everyone = 0;
+
+    for _ in 0..scale {
+        let people = (0..rng.gen_range(1..=5))
+            // Everyone answers "yes" to at least one question
+            .map(|_| rng.gen_range(1_u32..1 << 26))
+            .collect::<Vec<_>>();
+
+        anyone += people.iter().fold(0, |acc, p| acc | p).count_ones();
+        everyone += people.iter().fold(!0, |acc, p| acc & p).count_ones();
+
+        let group = people
+            .iter()
+            .map(|p| {
+                let mut line = ('a'..='z')
+                    .enumerate()
+                    .filter(|&(ix, _)| p >> ix & 1 == 1)
+                    .map(|(_, c)| c)
+                    .collect::<String>();
+                line.push('\n');
+                line
+            })
+            .collect::<String>();
+        groups.push(group);
+    }
+
+    Generated {
+        input: groups.join("\n"),
+        answers: vec![Some(Answer::from(anyone)), Some(Answer::from(everyone))],
+        params: Params::default(),
+    }
+}
@@ -0,0 +1,73 @@
+pub mod generate;
+
+use anyhow::Result;
+use aoc_common::parse::split_groups;
+use std::collections::HashSet;
+
+pub fn part1(input: &str) -> Result<usize> {
+    let unique_chars: Vec<HashSet<_>> = get_data(input)
+        .map(|g| g.lines().flat_map(|s| s.chars()).collect())
+        .collect();
+
+    Ok(len_sum(unique_chars))
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    let groups: Vec<Vec<HashSet<_>>> = get_data(input)
+        .map(|g| g.lines().map(|l| l.chars().collect()).collect())
+        .collect();
+
+    let group_intersections: Vec<HashSet<_>> = groups
+        .into_iter()
+        .map(|vhs| {
+            let mut vhs_iter = vhs.into_iter();
+            let first = vhs_iter.next().unwrap_or_default();
+            vhs_iter.fold(first, |acc, x| acc.intersection(&x).cloned().collect())
+        })
+        .collect();
+
+    Ok(len_sum(group_intersections))
+}
+
+pub fn parse(input: &str) -> Result<impl Sized + '_> {
+    Ok(get_data(input).collect::<Vec<_>>())
+}
+
+fn get_data(input: &str) -> impl Iterator<Item = &str> {
+    split_groups(input)
+}
+
+fn len_sum<T>(vhs: Vec<HashSet<T>>) -> usize {
+    vhs.into_iter().map(|hs| hs.len()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("../input/input.txt");
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+
+    #[test]
+    fn same_answers_for_lf_and_crlf() -> Result<()> {
+        let lf = INPUT.replace("\r\n", "\n");
+        let crlf = lf.replace('\n', "\r\n");
+
+        assert_eq!(part1(&lf)?, part1(&crlf)?);
+        assert_eq!(part2(&lf)?, part2(&crlf)?);
+        assert!(part2(&lf)? > 0);
+        Ok(())
+    }
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(EXAMPLE)?, 11);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(EXAMPLE)?, 6);
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+//! Bag rules without cycles, with the shiny gold bag somewhere in the middle
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+/// The number of rules in the real input
+pub const PUZZLE_SCALE: usize = 594;
+
+/// The numbers of rules, one per bag color
+pub const SCALES: RangeInclusive<usize> = 2..=ADJECTIVES.len() * COLORS.len();
+
+const MY_BAG: &str = "shiny gold";
+
+const ADJECTIVES: [&str; 30] = [
+    "bright", "clear", "dark", "dim", "dotted", "drab", "dull", "faded", "mirrored", "muted",
+    "pale", "plaid", "posh", "shiny", "striped", "vibrant", "wavy", "light", "bold", "calm",
+    "crisp", "deep", "dusty", "fancy", "glossy", "hazy", "misty", "quiet", "rough", "soft",
+];
+
+const COLORS: [&str; 30] = [
+    "aqua",
+    "beige",
+    "black",
+    "blue",
+    "bronze",
+    "brown",
+    "chartreuse",
+    "coral",
+    "crimson",
+    "cyan",
+    "fuchsia",
+    "gold",
+    "gray",
+    "green",
+    "indigo",
+    "lavender",
+    "lime",
+    "magenta",
+    "maroon",
+    "olive",
+    "orange",
+    "plum",
+    "purple",
+    "red",
+    "salmon",
+    "silver",
+    "tan",
+    "teal",
+    "tomato",
+    "violet",
+];
+
+/// The bags only contain the bags of the next layer, so the nesting is never deeper than this
+const LAYERS: usize = 8;
+
+/// Makes `scale` rules, at least 2 and at most 900
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    let count = scale.clamp(*SCALES.start(), *SCALES.end());
+
+    let mut names = ADJECTIVES
+        .iter()
+        .flat_map(|a| COLORS.iter().map(move |c| format!("{} {}", a, c)))
+        .filter(|n| n != MY_BAG)
+        .collect::<Vec<_>>();
+    names.shuffle(&mut rng);
+    names.truncate(count - 1);
+    // Our bag goes to the third layer, so it usually has both the outer and the inner bags
+    names.insert(count * 3 / LAYERS, MY_BAG.to_string());
+
+    let layer = |ix: usize| ix * LAYERS / count;
+    let contents = (0..count)
+        .map(|ix| {
+            let next_layer = (ix..count)
+                .filter(|&inner| layer(inner) == layer(ix) + 1)
+                .collect::<Vec<_>>();
+            let kinds = rng.gen_range(0..=3.min(next_layer.len()));
+            next_layer
+                .choose_multiple(&mut rng, kinds)
+                .map(|&inner| (rng.gen_range(1..=4), inner))
+                .collect::<Vec<(u64, usize)>>()
+        })
+        .collect::<Vec<_>>();
+
+    let me = names.iter().position(|n| n == MY_BAG);
+    // We may .unwrap() here, because we have just inserted our bag
+    let me = me.unwrap();
+
+    let mut rules = (0..count)
+        .map(|ix| {
+            format!(
+                "{} bags contain {}.\n",
+                names[ix],
+                describe(&names, &contents[ix])
+            )
+        })
+        .collect::<Vec<_>>();
+    rules.shuffle(&mut rng);
+
+    Generated {
+        input: rules.concat(),
+        answers: vec![
+            Some(Answer::from(outer_bags(&contents, me))),
+            Some(Answer::from(inner_bags(&contents, me, &mut HashMap::new()))),
+        ],
+        params: Params::default(),
+    }
+}
+
+fn describe(names: &[String], contents: &[(u64, usize)]) -> String {
+    match contents.is_empty() {
+        true => "no other bags".to_string(),
+        false => contents
+            .iter()
+            .map(|&(n, inner)| match n {
+                1 => format!("1 {} bag", names[inner]),
+                _ => format!("{} {} bags", n, names[inner]),
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
+
+/// The outer bags are all in the earlier layers, so one pass from our bag back to the first bag finds them all
+fn outer_bags(contents: &[Vec<(u64, usize)>], me: usize) -> usize {
+    let mut holds_me = vec![false; contents.len()];
+    holds_me[me] = true;
+
+    for outer in (0..me).rev() {
+        holds_me[outer] = contents[outer].iter().any(|&(_, inner)| holds_me[inner]);
+    }
+
+    holds_me.iter().filter(|&&h| h).count() - 1
+}
+
+fn inner_bags(contents: &[Vec<(u64, usize)>], bag: usize, memo: &mut HashMap<usize, u64>) -> u64 {
+    if let Some(&count) = memo.get(&bag) {
+        return count;
+    }
+
+    let count = contents[bag]
+        .iter()
+        .map(|&(n, inner)| n * (1 + inner_bags(contents, inner, memo)))
+        .sum();
+    memo.insert(bag, count);
+    count
+}
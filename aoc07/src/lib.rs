@@ -0,0 +1,204 @@
+pub mod generate;
+
+use anyhow::{anyhow, bail, Result};
+use aoc_common::parse::{parse_lines, parse_token, OrInvalid};
+use aoc_common::{Param, Params, ParseError};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+const MY_BAG: Param = Param {
+    name: "bag",
+    default: "shiny gold",
+    help: "The bag to carry",
+};
+
+pub const PARAMS: &[Param] = &[MY_BAG];
+
+pub fn part1(input: &str) -> Result<usize> {
+    part1_with(input, &Params::default())
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    part2_with(input, &Params::default())
+}
+
+pub fn part1_with(input: &str, params: &Params) -> Result<usize> {
+    let bags = get_data(input)?;
+
+    let mut should_contain = HashSet::new();
+    should_contain.insert(params.get::<String>(&MY_BAG)?);
+
+    loop {
+        let mut inserted = false;
+
+        for bag in bags.iter() {
+            let mut need_to_insert = false;
+
+            for (_, can_contain) in bag.contains.iter() {
+                if !should_contain.contains(&bag.name) && should_contain.contains(can_contain) {
+                    need_to_insert = true;
+                    break;
+                }
+            }
+
+            if need_to_insert {
+                should_contain.insert(bag.name.clone());
+                inserted = true;
+            }
+        }
+
+        if !inserted {
+            break;
+        }
+    }
+
+    Ok(should_contain.len() - 1)
+}
+
+pub fn part2_with(input: &str, params: &Params) -> Result<usize> {
+    let bags: HashMap<_, _> = get_data(input)?
+        .into_iter()
+        .map(|b| (b.name, b.contains))
+        .collect();
+    let my_bag: String = params.get(&MY_BAG)?;
+
+    bag_count(&bags, &my_bag, &mut HashMap::new())
+}
+
+pub fn parse(input: &str) -> Result<impl Sized> {
+    get_data(input)
+}
+
+/// Counts the bags inside the bag, remembering the counts of the bags we have already seen
+///
+/// A bag that is still being counted is `None` in `counts`, so we notice the bags that contain themselves.
+fn bag_count<'a>(
+    hm: &'a HashMap<String, Vec<(usize, String)>>,
+    name: &'a str,
+    counts: &mut HashMap<&'a str, Option<usize>>,
+) -> Result<usize> {
+    match counts.get(name) {
+        Some(Some(count)) => return Ok(*count),
+        Some(None) => bail!("The {} bag contains itself", name),
+        None => {}
+    }
+    counts.insert(name, None);
+
+    let contains = hm.get(name).or_invalid()?;
+
+    let mut count: usize = 0;
+
+    for (inner_count, inner_name) in contains {
+        count = bag_count(hm, inner_name, counts)?
+            .checked_add(1)
+            .and_then(|c| c.checked_mul(*inner_count))
+            .and_then(|c| c.checked_add(count))
+            .ok_or_else(|| anyhow!("The {} bag contains too many bags", name))?;
+    }
+
+    counts.insert(name, Some(count));
+    Ok(count)
+}
+
+// We should rather use &str here to reduce allocs, but I decided to go the easy way
+#[derive(Debug)]
+struct Bag {
+    name: String,
+    /// The counts and the names of the bags inside
+    contains: Vec<(usize, String)>,
+}
+
+impl FromStr for Bag {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut line_iter = line.split(" bags contain ");
+        // We may .unwrap() here, because split always gives at least one item
+        let name = line_iter.next().unwrap();
+        let rest = line_iter
+            .next()
+            .ok_or_else(|| ParseError::after(line, name, "\" bags contain \""))?;
+        let name = name.to_string();
+
+        let contains = match rest {
+            "no other bags." => vec![],
+            _ => {
+                let mut contents = vec![];
+
+                for content in rest.split(", ") {
+                    let mut content_iter = content.split_ascii_whitespace();
+                    let count = content_iter
+                        .next()
+                        .ok_or_else(|| ParseError::new(line, content, "a count"))?;
+                    let count: usize = parse_token(line, count, "a count")?;
+                    let name_part_1 = content_iter
+                        .next()
+                        .ok_or_else(|| ParseError::after(line, content, "a bag name"))?;
+                    let name_part_2 = content_iter
+                        .next()
+                        .ok_or_else(|| ParseError::after(line, content, "a bag name"))?;
+
+                    contents.push((count, format!("{} {}", name_part_1, name_part_2)));
+                }
+
+                contents
+            }
+        };
+
+        Ok(Bag { name, contains })
+    }
+}
+
+fn get_data(input: &str) -> Result<Vec<Bag>> {
+    Ok(parse_lines(input, Bag::from_str)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+    const EXAMPLE_2: &str = include_str!("../input/example2.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(EXAMPLE)?, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_examples() -> Result<()> {
+        assert_eq!(part2(EXAMPLE)?, 32);
+        assert_eq!(part2(EXAMPLE_2)?, 126);
+        Ok(())
+    }
+
+    #[test]
+    fn another_bag() -> Result<()> {
+        let params = Params::default().with(&MY_BAG, "muted yellow");
+        assert_eq!(part1_with(EXAMPLE, &params)?, 2);
+        assert_eq!(part2_with(EXAMPLE, &params)?, 75);
+        Ok(())
+    }
+
+    #[test]
+    fn parses_a_bag() -> Result<()> {
+        let bag = Bag::from_str("bright white bags contain 1 shiny gold bag, 2 faded blue bags.")?;
+        assert_eq!(bag.name, "bright white");
+        assert_eq!(
+            bag.contains,
+            [(1, "shiny gold".to_string()), (2, "faded blue".to_string())]
+        );
+
+        let bag = Bag::from_str("faded blue bags contain no other bags.")?;
+        assert!(bag.contains.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn a_bag_inside_itself_is_an_error() {
+        let input =
+            "shiny gold bags contain 1 dark red bag.\ndark red bags contain 2 shiny gold bags.";
+        assert!(part2(input).is_err());
+    }
+}
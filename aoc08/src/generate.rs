@@ -0,0 +1,55 @@
+//! Boot code that loops because of exactly one jump
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::Rng;
+use std::ops::RangeInclusive;
+
+/// The number of instructions in the real input
+pub const PUZZLE_SCALE: usize = 633;
+
+/// The lengths of the programs
+pub const SCALES: RangeInclusive<usize> = 2..=usize::MAX;
+
+/// Makes a program of `scale` instructions, at least 2
+///
+/// The program runs straight down except for one jump back, the one to fix.
+/// Every other jump is `jmp +1`, and the argument of every `nop` points back, so no other change stops the loop.
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    let count = scale.max(*SCALES.start());
+    let loop_jump = rng.gen_range(count / 2..count);
+
+    let mut program = String::new();
+    let mut before_loop_jump = 0;
+    let mut total = 0;
+
+    for ix in 0..count {
+        // The jumps back never leave the program
+        let back = ix as i32;
+        let instruction = match rng.gen_range(0..4) {
+            _ if ix == loop_jump => format!("jmp {:+}", -rng.gen_range(1..=back)),
+            0 => format!("nop {:+}", -rng.gen_range(0..=back)),
+            1 => "jmp +1".to_string(),
+            _ => {
+                let argument = rng.gen_range(-50..=50);
+                total += argument;
+                if ix < loop_jump {
+                    before_loop_jump += argument;
+                }
+                format!("acc {:+}", argument)
+            }
+        };
+        program.push_str(&instruction);
+        program.push('\n');
+    }
+
+    Generated {
+        input: program,
+        answers: vec![
+            Some(Answer::from(before_loop_jump)),
+            Some(Answer::from(total)),
+        ],
+        params: Params::default(),
+    }
+}
@@ -0,0 +1,222 @@
+pub mod generate;
+pub mod stream;
+
+use anyhow::{bail, Result};
+use aoc_common::parse::{parse_lines, parse_token, OrInvalid};
+use aoc_common::ParseError;
+use std::collections::HashSet;
+use std::str::FromStr;
+use tracing::{debug, trace};
+
+pub fn part1(input: &str) -> Result<i32> {
+    accumulator_at_loop(&get_data(input)?)
+}
+
+pub fn part2(input: &str) -> Result<i32> {
+    accumulator_at_end(&get_data(input)?)
+}
+
+pub fn parse(input: &str) -> Result<impl Sized> {
+    get_data(input)
+}
+
+/// The accumulator right before any instruction runs a second time
+fn accumulator_at_loop(operations: &[Operation]) -> Result<i32> {
+    let mut visited = HashSet::new();
+
+    let mut accumulator: i32 = 0;
+    let mut cursor: i32 = 0;
+
+    loop {
+        if visited.contains(&cursor) {
+            break;
+        }
+        visited.insert(cursor);
+
+        let operation = operations.get(cursor as usize).or_invalid()?;
+        trace!(cursor, accumulator, ?operation, "execute");
+        match operation {
+            Operation::Acc(n) => {
+                accumulator = accumulator.checked_add(*n).or_invalid()?;
+                cursor += 1;
+            }
+            Operation::Jmp(n) => {
+                cursor = cursor.checked_add(*n).or_invalid()?;
+            }
+            Operation::Nop(_) => {
+                cursor += 1;
+            }
+        }
+    }
+
+    Ok(accumulator)
+}
+
+/// The accumulator at the end of the program with one jmp or nop swapped, so that it terminates
+fn accumulator_at_end(operations: &[Operation]) -> Result<i32> {
+    let mut changed = None;
+    let mut change_tries = HashSet::new();
+
+    let mut visited = HashSet::new();
+
+    let mut accumulator: i32 = 0;
+    let mut cursor: i32 = 0;
+
+    loop {
+        if visited.contains(&cursor) {
+            // Every change on the way has been tried already, and the program still loops
+            if changed.is_none() {
+                bail!("No single change makes the program terminate");
+            }
+            debug!(changed, cursor, "loops anyway, restarting");
+            visited.clear();
+            accumulator = 0;
+            cursor = 0;
+            changed = None;
+            continue;
+        }
+        visited.insert(cursor);
+
+        let operation = operations.get(cursor as usize).or_invalid()?;
+        trace!(cursor, accumulator, ?operation, "execute");
+        match operation {
+            Operation::Acc(n) => {
+                accumulator = accumulator.checked_add(*n).or_invalid()?;
+                cursor += 1;
+            }
+            Operation::Jmp(n) => {
+                if changed.is_none() && !change_tries.contains(&(cursor as usize)) {
+                    changed = Some(cursor as usize);
+                    change_tries.insert(cursor as usize);
+                    debug!(cursor, "jmp changed to nop");
+                    cursor += 1;
+                } else {
+                    cursor = cursor.checked_add(*n).or_invalid()?;
+                }
+            }
+            Operation::Nop(n) => {
+                if changed.is_none() && !change_tries.contains(&(cursor as usize)) {
+                    changed = Some(cursor as usize);
+                    change_tries.insert(cursor as usize);
+                    debug!(cursor, "nop changed to jmp");
+                    cursor = cursor.checked_add(*n).or_invalid()?;
+                } else {
+                    cursor += 1;
+                }
+            }
+        }
+
+        if cursor as usize == operations.len() {
+            break;
+        }
+    }
+
+    Ok(accumulator)
+}
+
+#[derive(Debug)]
+enum Operation {
+    Acc(i32),
+    Jmp(i32),
+    Nop(i32),
+}
+
+impl FromStr for Operation {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut value_iter = value.split_ascii_whitespace();
+        let instruction = value_iter
+            .next()
+            .ok_or_else(|| ParseError::new(value, value, "acc, jmp or nop"))?;
+        let operation: fn(i32) -> Operation = match instruction {
+            "acc" => Operation::Acc,
+            "jmp" => Operation::Jmp,
+            "nop" => Operation::Nop,
+            _ => return Err(ParseError::new(value, instruction, "acc, jmp or nop")),
+        };
+
+        let argument = value_iter
+            .next()
+            .ok_or_else(|| ParseError::after(value, instruction, "an argument"))?;
+        // The positive arguments have one plus sign, and i32 would take a second one after it
+        let number = argument.strip_prefix('+').unwrap_or(argument);
+        if number.starts_with('+') {
+            return Err(ParseError::new(value, number, "a number"));
+        }
+        let argument = parse_token(value, number, "a number")?;
+
+        match value_iter.next() {
+            Some(extra) => Err(ParseError::new(value, extra, "the end of the line")),
+            None => Ok(operation(argument)),
+        }
+    }
+}
+
+fn get_data(input: &str) -> Result<Vec<Operation>> {
+    Ok(parse_lines(input, Operation::from_str)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::stream::same_answers;
+    use aoc_common::Params;
+
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(EXAMPLE)?, 5);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(EXAMPLE)?, 8);
+        Ok(())
+    }
+
+    #[test]
+    fn points_at_a_bad_operation() {
+        let line = "acx +1";
+        let error = Operation::from_str(line).err().unwrap();
+        assert_eq!(error.column, 1);
+        assert_eq!(error.expected, "acc, jmp or nop");
+
+        // A line without the mnemonic is about the mnemonic, not about the missing argument
+        let error = Operation::from_str("1946").err().unwrap();
+        assert_eq!(
+            (error.column, error.expected.as_str()),
+            (1, "acc, jmp or nop")
+        );
+    }
+
+    #[test]
+    fn takes_one_plus_sign() -> Result<()> {
+        assert!(matches!(Operation::from_str("acc +5")?, Operation::Acc(5)));
+        let error = Operation::from_str("acc ++5").err().unwrap();
+        assert_eq!((error.column, error.expected.as_str()), (6, "a number"));
+        Ok(())
+    }
+
+    #[test]
+    fn points_at_the_extra_tokens() {
+        let error = Operation::from_str("jmp -3 nop").err().unwrap();
+        assert_eq!(error.column, 8);
+        assert_eq!(error.expected, "the end of the line");
+    }
+
+    #[test]
+    fn streams_the_same_answers() -> Result<()> {
+        let parts = aoc_common::parts![part1, part2];
+        let stream_parts = aoc_common::stream_parts![stream::part1, stream::part2];
+        let params = Params::default();
+        same_answers(EXAMPLE, parts, stream_parts, &params)?;
+        for seed in 0..10 {
+            let input = generate::generate(seed, 300).input;
+            same_answers(&input, parts, stream_parts, &params)?;
+        }
+        Ok(())
+    }
+}
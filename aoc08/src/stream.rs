@@ -0,0 +1,25 @@
+//! The same parts, reading the program line by line
+//!
+//! The jumps go anywhere in the program, so we keep all of its operations, but not the text.
+
+use crate::{accumulator_at_end, accumulator_at_loop, Operation};
+use anyhow::Result;
+use aoc_common::stream::Source;
+use std::str::FromStr;
+
+pub fn part1(source: Source<'_>) -> Result<i32> {
+    accumulator_at_loop(&get_data(source)?)
+}
+
+pub fn part2(source: Source<'_>) -> Result<i32> {
+    accumulator_at_end(&get_data(source)?)
+}
+
+fn get_data(source: Source<'_>) -> Result<Vec<Operation>> {
+    let mut operations = vec![];
+    for line in source.lines()? {
+        let (number, line) = line?;
+        operations.push(Operation::from_str(&line).map_err(|e| e.at_line(number))?);
+    }
+    Ok(operations)
+}
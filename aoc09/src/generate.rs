@@ -0,0 +1,97 @@
+//! XMAS data with one number that breaks the rule and one contiguous set that sums to it
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::Rng;
+use std::ops::RangeInclusive;
+
+/// The number of numbers in the real input
+pub const PUZZLE_SCALE: usize = 1000;
+
+/// The numbers of numbers
+pub const SCALES: RangeInclusive<usize> = PREAMBLE + 1..=PUZZLE_SCALE;
+
+const PREAMBLE: usize = 25;
+
+/// Makes `scale` numbers, at least one more than the preamble and at most as many as in the real input
+///
+/// Every number after the preamble is the sum of two of the previous ones, except for the invalid one.
+/// The invalid one is the sum of a contiguous set before it, and we retry until no other set has the same sum.
+/// Each sum is at least twice the smallest number of the preamble, so not many more numbers fit into `u64`.
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    let count = scale.clamp(*SCALES.start(), *SCALES.end());
+
+    loop {
+        let mut numbers = (0..PREAMBLE)
+            .map(|_| rng.gen_range(1..=50))
+            .collect::<Vec<u64>>();
+        let invalid_ix = rng.gen_range(PREAMBLE..count);
+
+        while numbers.len() < count {
+            let window = &numbers[numbers.len() - PREAMBLE..];
+
+            let number = match numbers.len() == invalid_ix {
+                true => {
+                    let length = rng.gen_range(2..=numbers.len().min(10));
+                    let start = rng.gen_range(0..=numbers.len() - length);
+                    numbers[start..start + length].iter().sum()
+                }
+                false => {
+                    let mut sorted = window.to_vec();
+                    sorted.sort_unstable();
+                    // The smaller of two random picks prefers the small numbers, so the numbers grow slowly
+                    let mut pick = || rng.gen_range(0..PREAMBLE).min(rng.gen_range(0..PREAMBLE));
+                    let a = pick();
+                    let b = loop {
+                        let b = pick();
+                        if b != a {
+                            break b;
+                        }
+                    };
+                    sorted[a] + sorted[b]
+                }
+            };
+            numbers.push(number);
+        }
+
+        let invalid = numbers[invalid_ix];
+        let window = &numbers[invalid_ix - PREAMBLE..invalid_ix];
+        let is_sum = window
+            .iter()
+            .enumerate()
+            .any(|(ix, a)| window[ix + 1..].iter().any(|b| a + b == invalid));
+        let sets = contiguous_sets(&numbers, invalid);
+
+        if let (false, [set]) = (is_sum, sets.as_slice()) {
+            // We may .unwrap() here, because the sets have at least two numbers
+            let weakness = set.iter().min().unwrap() + set.iter().max().unwrap();
+
+            return Generated {
+                input: numbers.iter().map(|n| format!("{}\n", n)).collect(),
+                answers: vec![Some(Answer::from(invalid)), Some(Answer::from(weakness))],
+                params: Params::default(),
+            };
+        }
+    }
+}
+
+/// All the contiguous sets of at least two numbers with the given sum
+fn contiguous_sets(numbers: &[u64], sum: u64) -> Vec<&[u64]> {
+    let mut sets = vec![];
+
+    for start in 0..numbers.len() {
+        let mut total = numbers[start];
+        for (length, number) in numbers[start + 1..].iter().enumerate() {
+            total += number;
+            if total == sum {
+                sets.push(&numbers[start..start + length + 2]);
+            }
+            if total >= sum {
+                break;
+            }
+        }
+    }
+
+    sets
+}
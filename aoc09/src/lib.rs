@@ -0,0 +1,158 @@
+pub mod generate;
+pub mod stream;
+
+use anyhow::{anyhow, Result};
+use aoc_common::parse::{parse_lines, parse_token};
+use aoc_common::{Param, Params};
+use itertools::Itertools;
+use std::cmp::Ordering;
+
+const PREAMBLE: Param = Param {
+    name: "preamble",
+    default: "25",
+    help: "The number of the previous numbers that each number must be a sum of two of",
+};
+
+pub const PARAMS: &[Param] = &[PREAMBLE];
+
+pub fn part1(input: &str) -> Result<u64> {
+    part1_with(input, &Params::default())
+}
+
+pub fn part2(input: &str) -> Result<u64> {
+    part2_with(input, &Params::default())
+}
+
+pub fn part1_with(input: &str, params: &Params) -> Result<u64> {
+    find_invalid(&get_data(input)?, params.get(&PREAMBLE)?)
+}
+
+pub fn part2_with(input: &str, params: &Params) -> Result<u64> {
+    let numbers = get_data(input)?;
+    let invalid_number = find_invalid(&numbers, params.get(&PREAMBLE)?)?;
+    find_weakness(&numbers, invalid_number)
+}
+
+pub fn parse(input: &str) -> Result<impl Sized> {
+    get_data(input)
+}
+
+// The puzzle examples use a shorter preamble, so we pass it here
+fn find_invalid(numbers: &[u64], preamble: usize) -> Result<u64> {
+    for (idx, &number) in numbers.iter().enumerate() {
+        if idx < preamble {
+            continue;
+        }
+
+        // We may use the index because we checked the length
+        if numbers[(idx - preamble)..idx]
+            .iter()
+            .cloned()
+            .combinations(2)
+            // The sum of the big numbers may overflow, and then it's not the number for sure
+            .map(|v| v[0].checked_add(v[1]))
+            .all(|x| x != Some(number))
+        {
+            return Ok(number);
+        }
+    }
+
+    Err(no_invalid(preamble))
+}
+
+fn find_weakness(numbers: &[u64], invalid_number: u64) -> Result<u64> {
+    for (idx, number) in numbers.iter().enumerate() {
+        // I should have used slicing here to reduce allocs, but I decided to go the easy way
+        let mut current_numbers = vec![*number];
+
+        for next_number in numbers.iter().skip(idx + 1) {
+            current_numbers.push(*next_number);
+
+            let sum = current_numbers.iter().map(|&n| u128::from(n)).sum::<u128>();
+
+            match sum.cmp(&u128::from(invalid_number)) {
+                Ordering::Equal => {
+                    // We may .unwrap() here, because current_numbers is DEFINITELY not empty
+                    let min = *current_numbers.iter().min().unwrap();
+                    let max = *current_numbers.iter().max().unwrap();
+                    return weakness(min, max);
+                }
+                Ordering::Greater => {
+                    break;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Err(no_weakness(invalid_number))
+}
+
+fn no_invalid(preamble: usize) -> anyhow::Error {
+    anyhow!(
+        "Every number after the preamble is the sum of two of the {} numbers before it",
+        preamble
+    )
+}
+
+fn no_weakness(invalid_number: u64) -> anyhow::Error {
+    anyhow!(
+        "No two or more contiguous numbers sum to the invalid number {}",
+        invalid_number
+    )
+}
+
+fn weakness(min: u64, max: u64) -> Result<u64> {
+    min.checked_add(max)
+        .ok_or_else(|| anyhow!("The encryption weakness is too big"))
+}
+
+fn get_data(input: &str) -> Result<Vec<u64>> {
+    Ok(parse_lines(input, |line| {
+        parse_token(line, line, "a number")
+    })?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::stream::same_answers;
+
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+
+    #[test]
+    fn finds_the_invalid_number_of_the_example() -> Result<()> {
+        let numbers = get_data(EXAMPLE)?;
+        assert_eq!(find_invalid(&numbers, 5)?, 127);
+        Ok(())
+    }
+
+    #[test]
+    fn finds_the_weakness_of_the_example() -> Result<()> {
+        let numbers = get_data(EXAMPLE)?;
+        assert_eq!(find_weakness(&numbers, 127)?, 62);
+        Ok(())
+    }
+
+    #[test]
+    fn solves_the_example_with_its_preamble() -> Result<()> {
+        let params = Params::default().with(&PREAMBLE, 5);
+        assert_eq!(part1_with(EXAMPLE, &params)?, 127);
+        assert_eq!(part2_with(EXAMPLE, &params)?, 62);
+        Ok(())
+    }
+
+    #[test]
+    fn streams_the_same_answers() -> Result<()> {
+        let parts = aoc_common::parts![with part1_with, part2_with];
+        let stream_parts = aoc_common::stream_parts![with stream::part1_with, stream::part2_with];
+        let params = Params::default();
+        let example_params = Params::default().with(&PREAMBLE, 5);
+        same_answers(EXAMPLE, parts, stream_parts, &example_params)?;
+        for seed in 0..10 {
+            let input = generate::generate(seed, 300).input;
+            same_answers(&input, parts, stream_parts, &params)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+//! The same parts, reading the numbers line by line
+//!
+//! The first part keeps only the preamble before the number. The second part reads the input twice, first to find
+//! the invalid number and then to slide the window of the contiguous numbers over it, so it can't read stdin.
+
+use crate::{no_invalid, no_weakness, weakness, PREAMBLE};
+use anyhow::{bail, Result};
+use aoc_common::parse::parse_token;
+use aoc_common::stream::Source;
+use aoc_common::Params;
+use std::collections::VecDeque;
+
+pub fn part1(source: Source<'_>) -> Result<u64> {
+    part1_with(source, &Params::default())
+}
+
+pub fn part2(source: Source<'_>) -> Result<u64> {
+    part2_with(source, &Params::default())
+}
+
+pub fn part1_with(source: Source<'_>, params: &Params) -> Result<u64> {
+    find_invalid(source, params.get(&PREAMBLE)?)
+}
+
+pub fn part2_with(source: Source<'_>, params: &Params) -> Result<u64> {
+    if !source.rereadable() {
+        bail!("The second part reads the numbers twice, so they must be in a file, not stdin");
+    }
+    let invalid_number = find_invalid(source, params.get(&PREAMBLE)?)?;
+    find_weakness(source, invalid_number)
+}
+
+fn find_invalid(source: Source<'_>, preamble: usize) -> Result<u64> {
+    let mut previous: VecDeque<u64> = VecDeque::new();
+
+    for line in source.lines()? {
+        let (line_n, line) = line?;
+        let number = parse_number(line_n, &line)?;
+
+        if previous.len() == preamble {
+            let is_sum = (0..preamble).any(|i| {
+                (i + 1..preamble).any(|j| previous[i].checked_add(previous[j]) == Some(number))
+            });
+            if !is_sum {
+                return Ok(number);
+            }
+            previous.pop_front();
+        }
+        previous.push_back(number);
+    }
+
+    Err(no_invalid(preamble))
+}
+
+/// Finds the same range as the in-memory one: the first one by its start of at least two numbers
+///
+/// The numbers are never negative, so the end of the first range that reaches the invalid number only moves forward
+/// with the start, and the window keeps the numbers between them only.
+fn find_weakness(source: Source<'_>, invalid_number: u64) -> Result<u64> {
+    let target = u128::from(invalid_number);
+    let mut window = VecDeque::new();
+    let mut sum = 0_u128;
+
+    for line in source.lines()? {
+        let (line_n, line) = line?;
+        let number = parse_number(line_n, &line)?;
+        window.push_back(number);
+        sum += u128::from(number);
+
+        while window.len() >= 2 && sum >= target {
+            if sum == target {
+                // We may .unwrap() here, because the window has at least two numbers
+                let min = *window.iter().min().unwrap();
+                let max = *window.iter().max().unwrap();
+                return weakness(min, max);
+            }
+            // We may .unwrap() here for the same reason
+            sum -= u128::from(window.pop_front().unwrap());
+        }
+    }
+
+    Err(no_weakness(invalid_number))
+}
+
+fn parse_number(line_n: usize, line: &str) -> Result<u64> {
+    Ok(parse_token(line, line, "a number").map_err(|e| e.at_line(line_n))?)
+}
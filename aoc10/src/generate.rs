@@ -0,0 +1,72 @@
+//! Bags of adapters with the differences of 1 and 3 jolts, like the real ones
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::ops::RangeInclusive;
+
+/// The number of adapters in the real input
+pub const PUZZLE_SCALE: usize = 101;
+
+/// The numbers of adapters
+pub const SCALES: RangeInclusive<usize> = 1..=usize::MAX;
+
+/// Makes `scale` adapters, at least one
+///
+/// The runs of 1-jolt differences are at most 4 long, as in the real inputs.
+/// The arrangements are counted by going through the chain, so the count is `None` once it overflows `u64`.
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    let count = scale.max(*SCALES.start());
+
+    let mut differences = vec![];
+    while differences.len() < count {
+        let ones = rng.gen_range(0..=4);
+        differences.resize(differences.len() + ones, 1);
+        differences.push(3);
+    }
+    differences.truncate(count);
+
+    let mut adapters = differences
+        .iter()
+        .scan(0, |joltage, d| {
+            *joltage += d;
+            Some(*joltage)
+        })
+        .collect::<Vec<u32>>();
+
+    // The device is always 3 jolts above the last adapter
+    let ones = differences.iter().filter(|&&d| d == 1).count();
+    let threes = differences.iter().filter(|&&d| d == 3).count() + 1;
+
+    let arrangements = arrangements(&adapters);
+    adapters.shuffle(&mut rng);
+
+    Generated {
+        input: adapters.iter().map(|a| format!("{}\n", a)).collect(),
+        answers: vec![
+            Some(Answer::from(ones * threes)),
+            arrangements.map(Answer::from),
+        ],
+        params: Params::default(),
+    }
+}
+
+/// Counts the ways to reach each adapter from the outlet, given the sorted adapters
+fn arrangements(adapters: &[u32]) -> Option<u64> {
+    let mut chain = vec![(0, 1_u64)];
+
+    for &adapter in adapters {
+        // The joltages are distinct, so only the last three can be close enough
+        let ways = chain
+            .iter()
+            .rev()
+            .take(3)
+            .filter(|&&(joltage, _)| adapter - joltage <= 3)
+            .try_fold(0_u64, |sum, &(_, ways)| sum.checked_add(ways))?;
+        chain.push((adapter, ways));
+    }
+
+    chain.last().map(|&(_, ways)| ways)
+}
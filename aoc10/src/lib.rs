@@ -0,0 +1,87 @@
+pub mod generate;
+
+use anyhow::{anyhow, Result};
+use aoc_common::parse::{parse_lines, parse_token};
+use itertools::Itertools;
+
+pub fn part1(input: &str) -> Result<usize> {
+    let diffs = get_diffs(input)?;
+    Ok(diffs.iter().filter(|&&d| d == 1).count() * diffs.iter().filter(|&&d| d == 3).count())
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    let diffs = get_diffs(input)?;
+    let mut vars = diffs
+        .iter()
+        .dedup_with_count()
+        .filter(|(_, &d)| d == 1)
+        .map(|(n, _)| match n {
+            // It's basically 2 ^ (n - 1), but we have to consider that there are no 4+ jolt adapters
+            // There are only 1-3 jolt adapters
+            4 => 7, // Because of this, this 8 becomes a 7
+            3 => 4,
+            2 => 2,
+            _ => 1,
+        });
+    vars.try_fold(1_usize, |product, v| product.checked_mul(v))
+        .ok_or_else(|| anyhow!("Too many arrangements"))
+}
+
+pub fn parse(input: &str) -> Result<impl Sized> {
+    get_data(input)
+}
+
+fn get_data(input: &str) -> Result<Vec<u32>> {
+    let mut numbers: Vec<u32> = parse_lines(input, |l| parse_token(l, l, "a joltage"))?;
+
+    // Charging outlet
+    numbers.push(0);
+
+    numbers.sort_unstable();
+
+    // Device's built-in adapter
+    // The last element is the maximum, because the vector is sorted
+    // We may call .unwrap() here because there is at least 1 element in the vector
+    let max = numbers.last().copied().unwrap();
+    numbers.push(
+        max.checked_add(3)
+            .ok_or_else(|| anyhow!("The joltage of the device is too big"))?,
+    );
+
+    Ok(numbers)
+}
+
+fn get_diffs(input: &str) -> Result<Vec<u32>> {
+    let data = get_data(input)?;
+    Ok(data.windows(2).map(|w| w[1] - w[0]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+    const EXAMPLE_2: &str = include_str!("../input/example2.txt");
+
+    #[test]
+    fn part1_examples() -> Result<()> {
+        assert_eq!(part1(EXAMPLE)?, 7 * 5);
+        assert_eq!(part1(EXAMPLE_2)?, 22 * 10);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_examples() -> Result<()> {
+        assert_eq!(part2(EXAMPLE)?, 8);
+        assert_eq!(part2(EXAMPLE_2)?, 19208);
+        Ok(())
+    }
+
+    #[test]
+    fn points_at_a_bad_joltage() {
+        let error = part1("16\n10\n-5\n").err().unwrap();
+        let error = error.downcast_ref::<aoc_common::ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (Some(3), 1));
+        assert!(part1(&u32::MAX.to_string()).is_err());
+    }
+}
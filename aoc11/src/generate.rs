@@ -0,0 +1,123 @@
+//! Square seat layouts of any size
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::Rng;
+use std::ops::RangeInclusive;
+
+/// The number of rows in the real input
+pub const PUZZLE_SCALE: usize = 99;
+
+/// The sides of the layouts
+pub const SCALES: RangeInclusive<usize> = 1..=usize::MAX;
+
+const DIRECTIONS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Makes a layout of `scale` by `scale` places, at least one
+///
+/// The answers come from a simulation that finds the neighbors of each seat once, instead of looking around every round.
+/// Some layouts flip between two states forever, so we turn the flipping seats into floor until both rules settle. The
+/// number of seats only goes down, so this ends.
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    let side = scale.max(*SCALES.start());
+    let mut is_seat = (0..side * side)
+        .map(|_| rng.gen_bool(0.85))
+        .collect::<Vec<_>>();
+
+    let (part1, part2) = loop {
+        let settled = settle(&is_seat, &neighbors(&is_seat, side, false), 4).and_then(|part1| {
+            let part2 = settle(&is_seat, &neighbors(&is_seat, side, true), 5)?;
+            Ok((part1, part2))
+        });
+
+        match settled {
+            Ok(answers) => break answers,
+            Err(flipping) => flipping.into_iter().for_each(|ix| is_seat[ix] = false),
+        }
+    };
+
+    let mut input = String::new();
+    for row in is_seat.chunks(side) {
+        input.extend(row.iter().map(|&s| if s { 'L' } else { '.' }));
+        input.push('\n');
+    }
+
+    Generated {
+        input,
+        answers: vec![Some(Answer::from(part1)), Some(Answer::from(part2))],
+        params: Params::default(),
+    }
+}
+
+/// The seats next to each seat, or the first seats in sight if `far`
+fn neighbors(is_seat: &[bool], side: usize, far: bool) -> Vec<Vec<usize>> {
+    (0..is_seat.len())
+        .map(|ix| {
+            DIRECTIONS
+                .iter()
+                .filter_map(|&(dy, dx)| {
+                    let (mut y, mut x) = ((ix / side) as isize, (ix % side) as isize);
+                    loop {
+                        y += dy;
+                        x += dx;
+                        if y < 0 || x < 0 || y >= side as isize || x >= side as isize {
+                            return None;
+                        }
+                        let seen = y as usize * side + x as usize;
+                        if is_seat[seen] {
+                            return Some(seen);
+                        }
+                        if !far {
+                            return None;
+                        }
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Runs the rounds until nothing changes and counts the occupied seats
+///
+/// If the seats never settle, the error has the seats that flip between the two states.
+fn settle(
+    is_seat: &[bool],
+    neighbors: &[Vec<usize>],
+    tolerance: usize,
+) -> Result<usize, Vec<usize>> {
+    let mut occupied = vec![false; is_seat.len()];
+    let mut two_rounds_ago = None;
+
+    loop {
+        let next = (0..is_seat.len())
+            .map(|ix| {
+                let around = neighbors[ix].iter().filter(|&&n| occupied[n]).count();
+                match (is_seat[ix], occupied[ix]) {
+                    (false, _) => false,
+                    (true, true) => around < tolerance,
+                    (true, false) => around == 0,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if next == occupied {
+            return Ok(occupied.iter().filter(|&&o| o).count());
+        }
+        if two_rounds_ago.as_ref() == Some(&next) {
+            return Err((0..next.len())
+                .filter(|&ix| next[ix] != occupied[ix])
+                .collect());
+        }
+        two_rounds_ago = Some(std::mem::replace(&mut occupied, next));
+    }
+}
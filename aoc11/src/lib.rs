@@ -0,0 +1,82 @@
+pub mod generate;
+
+use anyhow::{bail, Result};
+use aoc_common::automaton::{
+    Automaton, LineOfSight, Moore, Neighborhood, Outcome, Rule, SquareGrid,
+};
+use aoc_common::grid::Grid;
+
+pub fn part1(input: &str) -> Result<usize> {
+    // An empty seat is taken if there are no people around, and people leave if there are 4 or more of them
+    process(input, Moore, Rule::new(&[0], &[0, 1, 2, 3]))
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    process(input, LineOfSight, Rule::new(&[0], &[0, 1, 2, 3, 4]))
+}
+
+pub fn parse(input: &str) -> Result<impl Sized> {
+    get_seats(input)
+}
+
+fn process(input: &str, neighborhood: impl Neighborhood<SquareGrid>, rule: Rule) -> Result<usize> {
+    let seats = get_seats(input)?;
+
+    let occupied = seats
+        .positions()
+        .filter(|&p| seats[p] == Seat::Occupied)
+        .collect::<Vec<_>>();
+    let topology = SquareGrid::new(seats.map(|s| s != &Seat::Floor));
+    let mut waiting_area = Automaton::new(topology, neighborhood, rule, occupied);
+
+    match waiting_area.run() {
+        Outcome::Fixpoint => Ok(waiting_area.live_count()),
+        Outcome::Cycle { .. } => bail!("The seats never settle"),
+    }
+}
+
+fn get_seats(input: &str) -> Result<Grid<Seat>> {
+    Ok(Grid::parse(input, Seat::from_char, "a seat or the floor")?)
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Seat {
+    Floor,
+    Empty,
+    Occupied,
+}
+
+impl Seat {
+    fn from_char(value: char) -> Option<Self> {
+        match value {
+            '.' => Some(Seat::Floor),
+            'L' => Some(Seat::Empty),
+            '#' => Some(Seat::Occupied),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(EXAMPLE)?, 37);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(EXAMPLE)?, 26);
+        Ok(())
+    }
+
+    #[test]
+    fn seats_that_never_settle_are_an_error() {
+        assert!(part1("..LL.\n.LLLL\nLLLLL\nLLLLL\n.LLL.\n").is_err());
+    }
+}
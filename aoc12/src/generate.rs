@@ -0,0 +1,74 @@
+//! Navigation instructions of any length
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::Rng;
+use std::ops::RangeInclusive;
+
+/// The number of instructions in the real input
+pub const PUZZLE_SCALE: usize = 776;
+
+/// The numbers of instructions
+pub const SCALES: RangeInclusive<usize> = 0..=usize::MAX;
+
+/// Makes `scale` instructions
+///
+/// The answers come from the positions as vectors, where a turn to the right is `(x, y)` to `(y, -x)`.
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    let mut input = String::new();
+
+    let mut ship = (0_i64, 0_i64);
+    let mut heading = (1_i64, 0_i64);
+    let mut waypoint_ship = (0_i64, 0_i64);
+    let mut waypoint = (10_i64, 1_i64);
+
+    for _ in 0..scale {
+        let action = ['N', 'S', 'E', 'W', 'L', 'R', 'F'][rng.gen_range(0..7)];
+        let value = match action {
+            'L' | 'R' => 90 * rng.gen_range(1..=3),
+            _ => rng.gen_range(1..=100),
+        };
+        input.push_str(&format!("{}{}\n", action, value));
+
+        let by = i64::from(value);
+        match action {
+            'L' | 'R' => {
+                // A left turn is three right turns
+                let turns = value / 90 * if action == 'R' { 1 } else { 3 };
+                for _ in 0..turns {
+                    heading = (heading.1, -heading.0);
+                    waypoint = (waypoint.1, -waypoint.0);
+                }
+            }
+            'F' => {
+                ship = (ship.0 + heading.0 * by, ship.1 + heading.1 * by);
+                waypoint_ship = (
+                    waypoint_ship.0 + waypoint.0 * by,
+                    waypoint_ship.1 + waypoint.1 * by,
+                );
+            }
+            _ => {
+                let direction = match action {
+                    'N' => (0, 1),
+                    'S' => (0, -1),
+                    'E' => (1, 0),
+                    _ => (-1, 0),
+                };
+                ship = (ship.0 + direction.0 * by, ship.1 + direction.1 * by);
+                waypoint = (waypoint.0 + direction.0 * by, waypoint.1 + direction.1 * by);
+            }
+        }
+    }
+
+    Generated {
+        input,
+        answers: vec![
+            Some(Answer::from(ship.0.unsigned_abs() + ship.1.unsigned_abs())),
+            Some(Answer::from(
+                waypoint_ship.0.unsigned_abs() + waypoint_ship.1.unsigned_abs(),
+            )),
+        ],
+        params: Params::default(),
+    }
+}
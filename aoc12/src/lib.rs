@@ -0,0 +1,257 @@
+pub mod generate;
+pub mod stream;
+
+use anyhow::{anyhow, Result};
+use aoc_common::parse::{parse_lines, parse_token};
+use aoc_common::ParseError;
+use std::str::FromStr;
+
+pub fn part1(input: &str) -> Result<u64> {
+    process(input, Ship::process_part1)
+}
+
+pub fn part2(input: &str) -> Result<u64> {
+    process(input, Ship::process_part2)
+}
+
+pub fn parse(input: &str) -> Result<impl Sized> {
+    get_data(input)
+}
+
+fn process(input: &str, process_fn: impl Fn(&mut Ship, Instruction) -> Result<()>) -> Result<u64> {
+    let mut ship = Ship::new();
+    for instruction in get_data(input)? {
+        process_fn(&mut ship, instruction)?;
+    }
+    ship.manhattan_distance()
+}
+
+fn get_data(input: &str) -> Result<Vec<Instruction>> {
+    Ok(parse_lines(input, Instruction::from_str)?)
+}
+
+#[derive(Clone, Copy)]
+enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    fn rotate_left(self) -> Self {
+        match self {
+            Direction::North => Direction::West,
+            Direction::West => Direction::South,
+            Direction::South => Direction::East,
+            Direction::East => Direction::North,
+        }
+    }
+
+    fn rotate_right(self) -> Self {
+        match self {
+            Direction::North => Direction::East,
+            Direction::West => Direction::North,
+            Direction::South => Direction::West,
+            Direction::East => Direction::South,
+        }
+    }
+}
+
+struct Ship {
+    direction: Direction,
+    x: i64,
+    y: i64,
+    waypoint_x: i64,
+    waypoint_y: i64,
+}
+
+/// Moves the coordinate `times` times by `by`, unless the ship or the waypoint leaves the `i64` world
+fn moved(coordinate: i64, by: i64, times: u32) -> Result<i64> {
+    by.checked_mul(i64::from(times))
+        .and_then(|by| coordinate.checked_add(by))
+        .ok_or_else(|| anyhow!("The ship sails too far"))
+}
+
+fn negated(coordinate: i64) -> Result<i64> {
+    coordinate
+        .checked_neg()
+        .ok_or_else(|| anyhow!("The ship sails too far"))
+}
+
+impl Ship {
+    fn new() -> Self {
+        Ship {
+            direction: Direction::East,
+            x: 0,
+            y: 0,
+            waypoint_x: 10,
+            waypoint_y: 1,
+        }
+    }
+
+    fn step(&mut self, direction: Direction, value: u32) -> Result<()> {
+        match direction {
+            Direction::North => {
+                self.y = moved(self.y, 1, value)?;
+            }
+            Direction::West => {
+                self.x = moved(self.x, -1, value)?;
+            }
+            Direction::South => {
+                self.y = moved(self.y, -1, value)?;
+            }
+            Direction::East => {
+                self.x = moved(self.x, 1, value)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn process_part1(&mut self, instruction: Instruction) -> Result<()> {
+        match instruction {
+            Instruction::North(v) => {
+                self.step(Direction::North, v)?;
+            }
+            Instruction::South(v) => {
+                self.step(Direction::South, v)?;
+            }
+            Instruction::East(v) => {
+                self.step(Direction::East, v)?;
+            }
+            Instruction::West(v) => {
+                self.step(Direction::West, v)?;
+            }
+            Instruction::Forward(v) => {
+                self.step(self.direction, v)?;
+            }
+            // Four quarter turns make a full turn, so we skip them
+            Instruction::Left(v) => {
+                let v = v / 90 % 4;
+                for _ in 0..v {
+                    self.direction = self.direction.rotate_left();
+                }
+            }
+            Instruction::Right(v) => {
+                let v = v / 90 % 4;
+                for _ in 0..v {
+                    self.direction = self.direction.rotate_right();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn process_part2(&mut self, instruction: Instruction) -> Result<()> {
+        match instruction {
+            Instruction::North(v) => {
+                self.waypoint_y = moved(self.waypoint_y, 1, v)?;
+            }
+            Instruction::South(v) => {
+                self.waypoint_y = moved(self.waypoint_y, -1, v)?;
+            }
+            Instruction::East(v) => {
+                self.waypoint_x = moved(self.waypoint_x, 1, v)?;
+            }
+            Instruction::West(v) => {
+                self.waypoint_x = moved(self.waypoint_x, -1, v)?;
+            }
+            Instruction::Left(v) => {
+                let v = v / 90 % 4;
+                for _ in 0..v {
+                    let wp_x = self.waypoint_x;
+                    self.waypoint_x = negated(self.waypoint_y)?;
+                    self.waypoint_y = wp_x;
+                }
+            }
+            Instruction::Right(v) => {
+                let v = v / 90 % 4;
+                for _ in 0..v {
+                    let wp_x = self.waypoint_x;
+                    self.waypoint_x = self.waypoint_y;
+                    self.waypoint_y = negated(wp_x)?;
+                }
+            }
+            Instruction::Forward(v) => {
+                self.x = moved(self.x, self.waypoint_x, v)?;
+                self.y = moved(self.y, self.waypoint_y, v)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn manhattan_distance(&self) -> Result<u64> {
+        self.x
+            .unsigned_abs()
+            .checked_add(self.y.unsigned_abs())
+            .ok_or_else(|| anyhow!("The ship sails too far"))
+    }
+}
+
+enum Instruction {
+    North(u32),
+    South(u32),
+    East(u32),
+    West(u32),
+    Left(u32),
+    Right(u32),
+    Forward(u32),
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        const ACTIONS: &str = "N, S, E, W, L, R or F";
+
+        let mut value_chars = line.chars();
+        let action = value_chars
+            .next()
+            .ok_or_else(|| ParseError::new(line, line, ACTIONS))?;
+        let value = parse_token(line, value_chars.as_str(), "a number")?;
+        match action {
+            'N' => Ok(Instruction::North(value)),
+            'S' => Ok(Instruction::South(value)),
+            'E' => Ok(Instruction::East(value)),
+            'W' => Ok(Instruction::West(value)),
+            'L' => Ok(Instruction::Left(value)),
+            'R' => Ok(Instruction::Right(value)),
+            'F' => Ok(Instruction::Forward(value)),
+            _ => Err(ParseError::new(line, &line[..action.len_utf8()], ACTIONS)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::stream::same_answers;
+    use aoc_common::Params;
+
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(EXAMPLE)?, 25);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(EXAMPLE)?, 286);
+        Ok(())
+    }
+
+    #[test]
+    fn streams_the_same_answers() -> Result<()> {
+        let parts = aoc_common::parts![part1, part2];
+        let stream_parts = aoc_common::stream_parts![stream::part1, stream::part2];
+        let params = Params::default();
+        same_answers(EXAMPLE, parts, stream_parts, &params)?;
+        for seed in 0..10 {
+            let input = generate::generate(seed, 300).input;
+            same_answers(&input, parts, stream_parts, &params)?;
+        }
+        Ok(())
+    }
+}
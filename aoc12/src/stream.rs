@@ -0,0 +1,27 @@
+//! The same parts, reading the navigation instructions line by line
+
+use crate::{Instruction, Ship};
+use anyhow::Result;
+use aoc_common::stream::Source;
+use std::str::FromStr;
+
+pub fn part1(source: Source<'_>) -> Result<u64> {
+    process(source, Ship::process_part1)
+}
+
+pub fn part2(source: Source<'_>) -> Result<u64> {
+    process(source, Ship::process_part2)
+}
+
+fn process(
+    source: Source<'_>,
+    process_fn: impl Fn(&mut Ship, Instruction) -> Result<()>,
+) -> Result<u64> {
+    let mut ship = Ship::new();
+    for line in source.lines()? {
+        let (number, line) = line?;
+        let instruction = Instruction::from_str(&line).map_err(|e| e.at_line(number))?;
+        process_fn(&mut ship, instruction)?;
+    }
+    ship.manhattan_distance()
+}
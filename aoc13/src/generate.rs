@@ -0,0 +1,93 @@
+//! Bus schedules with prime IDs, like the real ones
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::ops::RangeInclusive;
+
+/// The number of buses in the real input
+pub const PUZZLE_SCALE: usize = 9;
+
+/// The numbers of buses
+pub const SCALES: RangeInclusive<usize> = 1..=usize::MAX;
+
+/// Makes a schedule of `scale` buses, at least one, or fewer if the product of the IDs would overflow `i64`
+///
+/// The second answer comes from sieving: we step by the product of the IDs we have already matched.
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+
+    let mut primes = primes_below(1000).filter(|&p| p >= 11).collect::<Vec<_>>();
+    primes.shuffle(&mut rng);
+
+    let mut ids = vec![];
+    let mut product = 1_u64;
+    for prime in primes {
+        if ids.len() == scale.max(*SCALES.start()) {
+            break;
+        }
+        if let Some(p) = product.checked_mul(prime).filter(|&p| p <= i64::MAX as u64) {
+            ids.push(prime);
+            product = p;
+        }
+    }
+
+    let mut schedule = vec![];
+    let mut buses = vec![];
+    for (ix, &id) in ids.iter().enumerate() {
+        if ix > 0 {
+            let gap = rng.gen_range(0..10);
+            schedule.resize(schedule.len() + gap, "x".to_string());
+        }
+        buses.push((schedule.len() as u64, id));
+        schedule.push(id.to_string());
+    }
+
+    let earliest = loop {
+        let earliest = rng.gen_range(1..10_000_000_u64);
+        let mut waits = ids
+            .iter()
+            .map(|&id| ((id - earliest % id) % id, id))
+            .collect::<Vec<_>>();
+        waits.sort_unstable();
+        // Two buses leaving at the same time would make the answer ambiguous
+        if waits.len() == 1 || waits[0].0 != waits[1].0 {
+            break earliest;
+        }
+    };
+    // We may .unwrap() here, because there is at least one bus
+    let (wait, id) = ids
+        .iter()
+        .map(|&id| ((id - earliest % id) % id, id))
+        .min()
+        .unwrap();
+
+    let mut timestamp = 0;
+    let mut step = 1;
+    for &(offset, id) in &buses {
+        while (timestamp + offset) % id != 0 {
+            timestamp += step;
+        }
+        step *= id;
+    }
+
+    Generated {
+        input: format!("{}\n{}\n", earliest, schedule.join(",")),
+        answers: vec![Some(Answer::from(wait * id)), Some(Answer::from(timestamp))],
+        params: Params::default(),
+    }
+}
+
+/// https://en.wikipedia.org/wiki/Sieve_of_Eratosthenes
+fn primes_below(limit: usize) -> impl Iterator<Item = u64> {
+    let mut is_prime = vec![true; limit];
+    for n in 2..limit {
+        if is_prime[n] {
+            for multiple in (n * n..limit).step_by(n) {
+                is_prime[multiple] = false;
+            }
+        }
+    }
+    (2..limit).filter(move |&n| is_prime[n]).map(|n| n as u64)
+}
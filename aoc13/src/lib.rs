@@ -0,0 +1,113 @@
+pub mod generate;
+
+use anyhow::{anyhow, Result};
+use aoc_common::number_theory::crt;
+use aoc_common::parse::parse_token;
+use aoc_common::ParseError;
+
+pub fn part1(input: &str) -> Result<u64> {
+    let (earliest_timestamp, bus_ids) = get_data(input)?;
+    let earliest_timestamp = u64::from(earliest_timestamp);
+    let (min_id, min_timestamp) = bus_ids
+        .into_iter()
+        .map(|(_, id)| {
+            let id = u64::from(id);
+            // The first departure at or after the earliest timestamp, but not at zero
+            let timestamp = (earliest_timestamp.div_ceil(id) * id).max(id);
+            (id, timestamp)
+        })
+        .min_by_key(|x| x.1)
+        .ok_or_else(|| anyhow!("There are no buses, only x"))?;
+
+    Ok((min_timestamp - earliest_timestamp) * min_id)
+}
+
+pub fn part2(input: &str) -> Result<u64> {
+    // The bus with the offset ix leaves at t + ix, so t == -ix modulo its ID
+    let congruences = get_data(input)?
+        .1
+        .into_iter()
+        .map(|(ix, id)| {
+            let id = u64::from(id);
+            ((id - ix as u64 % id) % id, id)
+        })
+        .collect::<Vec<_>>();
+
+    let (timestamp, _) = crt(&congruences)
+        .ok_or_else(|| anyhow!("The buses never line up, or the timestamp is too big"))?;
+
+    Ok(timestamp)
+}
+
+pub fn parse(input: &str) -> Result<impl Sized> {
+    Ok(get_data(input)?)
+}
+
+/// The earliest timestamp and the bus IDs with their offsets, skipping the `x`s
+fn get_data(input: &str) -> Result<(u32, Vec<(usize, u32)>), ParseError> {
+    let mut lines = input.lines();
+
+    let line = lines.next().unwrap_or_default();
+    let earliest_timestamp = parse_token(line, line, "a timestamp").map_err(|e| e.at_line(1))?;
+
+    let line = lines
+        .next()
+        .ok_or_else(|| ParseError::new("", "", "the bus IDs").at_line(2))?;
+    let mut bus_ids = vec![];
+    for (ix, id) in line.split(',').enumerate() {
+        match id {
+            "x" => {}
+            _ => match parse_token(line, id, "a bus ID or x").map_err(|e| e.at_line(2))? {
+                0 => return Err(ParseError::new(line, id, "a bus ID other than 0").at_line(2)),
+                bus_id => bus_ids.push((ix, bus_id)),
+            },
+        }
+    }
+
+    Ok((earliest_timestamp, bus_ids))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(EXAMPLE)?, 295);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_examples() -> Result<()> {
+        assert_eq!(part2(EXAMPLE)?, 1068781);
+        // Only the second line matters for the second part
+        assert_eq!(part2("0\n17,x,13,19")?, 3417);
+        assert_eq!(part2("0\n67,7,59,61")?, 754018);
+        assert_eq!(part2("0\n67,x,7,59,61")?, 779210);
+        assert_eq!(part2("0\n67,7,x,59,61")?, 1261476);
+        assert_eq!(part2("0\n1789,37,47,1889")?, 1202161486);
+        Ok(())
+    }
+
+    #[test]
+    fn ids_with_common_factors() -> Result<()> {
+        assert_eq!(part2("0\n4,x,6")?, 4);
+        // The first bus leaves at the even timestamps and the second one needs an odd one
+        assert!(part2("0\n4,6").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn points_at_the_bad_id() {
+        let position = |input| {
+            let error = get_data(input).err().unwrap();
+            (error.line, error.column)
+        };
+        assert_eq!(position("939\n7,13,x,y,59"), (Some(2), 8));
+        assert_eq!(position("939\n7,0"), (Some(2), 3));
+        assert_eq!(position("soon\n7"), (Some(1), 1));
+        assert_eq!(position("939"), (Some(2), 1));
+    }
+}
@@ -0,0 +1,75 @@
+//! Docking programs with a few floating bits in each mask
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::Rng;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+/// The number of lines in the real input
+pub const PUZZLE_SCALE: usize = 577;
+
+/// The lengths of the programs
+pub const SCALES: RangeInclusive<usize> = 1..=usize::MAX;
+
+const BITS: usize = 36;
+
+/// Makes a program of `scale` lines, at least one, with at most 9 floating bits per mask like the real ones
+///
+/// The second answer goes through the floating addresses with the submask trick, without any strings.
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    let mut input = String::new();
+
+    let (mut ones, mut floating) = (0_u64, 0_u64);
+    let mut values = HashMap::new();
+    let mut floating_values = HashMap::new();
+
+    for ix in 0..scale.max(*SCALES.start()) {
+        if ix == 0 || rng.gen_bool(0.2) {
+            ones = rng.gen_range(0..1 << BITS);
+            floating = 0;
+            for _ in 0..rng.gen_range(1..=9) {
+                floating |= 1 << rng.gen_range(0..BITS);
+            }
+            ones &= !floating;
+
+            let mask = (0..BITS)
+                .rev()
+                .map(|bit| match (floating >> bit & 1, ones >> bit & 1) {
+                    (1, _) => 'X',
+                    (_, 1) => '1',
+                    _ => '0',
+                })
+                .collect::<String>();
+            input.push_str(&format!("mask = {}\n", mask));
+            continue;
+        }
+
+        let address = rng.gen_range(0..1 << 16);
+        let value = rng.gen_range(0..1 << BITS);
+        input.push_str(&format!("mem[{}] = {}\n", address, value));
+
+        // The floating bits keep the value bits as they are
+        values.insert(address, value & floating | ones);
+
+        let base = (address | ones) & !floating;
+        let mut submask = floating;
+        loop {
+            floating_values.insert(base | submask, value);
+            if submask == 0 {
+                break;
+            }
+            submask = (submask - 1) & floating;
+        }
+    }
+
+    Generated {
+        input,
+        answers: vec![
+            Some(Answer::from(values.values().sum::<u64>())),
+            Some(Answer::from(floating_values.values().sum::<u64>())),
+        ],
+        params: Params::default(),
+    }
+}
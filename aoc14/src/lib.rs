@@ -0,0 +1,231 @@
+pub mod generate;
+pub mod stream;
+
+use anyhow::{anyhow, bail, Context, Result};
+use aoc_common::parse::parse_token;
+use aoc_common::ParseError;
+use itertools::Itertools;
+use std::collections::HashMap;
+
+pub fn part1(input: &str) -> Result<u64> {
+    process(input, false)
+}
+
+pub fn part2(input: &str) -> Result<u64> {
+    process(input, true)
+}
+
+/// Each write goes to 2 to the power of this many addresses, so we don't let it grow without limit
+const MAX_FLOATING_BITS: usize = 16;
+
+pub fn parse(input: &str) -> Result<impl Sized + '_> {
+    get_data(input)
+}
+
+enum VariableMask {
+    Zero(u64),
+    Floating(Vec<u64>),
+}
+
+enum Instruction<'a> {
+    Mask(&'a str),
+    Write { key: u64, value: u64 },
+}
+
+fn get_data(input: &str) -> Result<Vec<Instruction<'_>>> {
+    // The masks borrow the input, which parse_lines doesn't allow
+    Ok(input
+        .lines()
+        .enumerate()
+        .map(|(ix, line)| get_instruction(line).map_err(|e| e.at_line(ix + 1)))
+        .collect::<Result<_, _>>()?)
+}
+
+/// Parses `mask = X01...` with 36 bits or `mem[8] = 11`
+fn get_instruction(line: &str) -> Result<Instruction<'_>, ParseError> {
+    if let Some(mask) = line.strip_prefix("mask = ") {
+        if let Some(ix) = mask.find(|c| !matches!(c, '0' | '1' | 'X')) {
+            return Err(ParseError::new(line, &mask[ix..], "0, 1 or X"));
+        }
+        return match mask.len() {
+            36 => Ok(Instruction::Mask(mask)),
+            _ => Err(ParseError::new(line, mask, "36 bits")),
+        };
+    }
+
+    let rest = line
+        .strip_prefix("mem[")
+        .ok_or_else(|| ParseError::new(line, line, "mask = ... or mem[...] = ..."))?;
+    let (key, value) = match rest.find("] = ") {
+        // We may use the indices here, because "] = " is ASCII
+        Some(ix) => (&rest[..ix], &rest[ix + 4..]),
+        None => return Err(ParseError::new(line, rest, "an address and \"] = \"")),
+    };
+
+    Ok(Instruction::Write {
+        key: parse_token(line, key, "an address")?,
+        value: parse_token(line, value, "a value")?,
+    })
+}
+
+fn process(input: &str, floating: bool) -> Result<u64> {
+    let mut program = Program::new(floating);
+    // Each line is one instruction
+    for (ix, instruction) in get_data(input)?.into_iter().enumerate() {
+        program
+            .execute(instruction)
+            .with_context(|| format!("Line {}", ix + 1))?;
+    }
+    program.sum()
+}
+
+/// The masks and the memory of the program, one instruction at a time
+struct Program {
+    floating: bool,
+    current_one_mask: u64,
+    current_variable_mask: VariableMask,
+    memory: HashMap<u64, u64>,
+}
+
+impl Program {
+    fn new(floating: bool) -> Self {
+        Program {
+            floating,
+            current_one_mask: 0,
+            current_variable_mask: match floating {
+                false => VariableMask::Zero(0),
+                true => VariableMask::Floating(vec![]),
+            },
+            memory: HashMap::new(),
+        }
+    }
+
+    fn execute(&mut self, instruction: Instruction<'_>) -> Result<()> {
+        match instruction {
+            Instruction::Mask(mask) => {
+                self.current_one_mask = u64::from_str_radix(&mask.replace("X", "0"), 2)?;
+                match self.floating {
+                    false => {
+                        self.current_variable_mask =
+                            VariableMask::Zero(u64::from_str_radix(&mask.replace("X", "1"), 2)?)
+                    }
+                    true => {
+                        let current_floating_mask = mask.replace("1", "0").replace("X", "1");
+                        let floating_bits = current_floating_mask.matches('1').count();
+                        if floating_bits > MAX_FLOATING_BITS {
+                            bail!(
+                                "The mask has {} floating bits, but at most {} are supported, \
+                                 because each write goes to 2 to the power of that many addresses",
+                                floating_bits,
+                                MAX_FLOATING_BITS
+                            );
+                        }
+                        // Ideally, this should be solved using tricky bitwise logic, but here it's just string manipulation
+                        self.current_variable_mask = VariableMask::Floating(
+                            (0..current_floating_mask.len())
+                                .map(|i| b'0'..=current_floating_mask.as_bytes()[i])
+                                .multi_cartesian_product()
+                                .flat_map(String::from_utf8)
+                                .flat_map(|v| u64::from_str_radix(&v, 2))
+                                .collect::<Vec<_>>(),
+                        );
+                    }
+                }
+            }
+            Instruction::Write { key, value } => match self.current_variable_mask {
+                VariableMask::Zero(current_zero_mask) => {
+                    *self.memory.entry(key).or_insert(0) =
+                        value & current_zero_mask | self.current_one_mask;
+                }
+                VariableMask::Floating(ref current_floating_masks) => {
+                    for floating_mask in current_floating_masks.iter() {
+                        *self
+                            .memory
+                            .entry((key | self.current_one_mask) ^ floating_mask)
+                            .or_insert(0) = value;
+                    }
+                }
+            },
+        }
+        Ok(())
+    }
+
+    fn sum(&self) -> Result<u64> {
+        self.memory
+            .values()
+            .try_fold(0_u64, |sum, &value| sum.checked_add(value))
+            .ok_or_else(|| anyhow!("The sum of the memory values is too big"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::stream::same_answers;
+    use aoc_common::Params;
+
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+    const EXAMPLE_2: &str = include_str!("../input/example2.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(EXAMPLE)?, 165);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(EXAMPLE_2)?, 208);
+        Ok(())
+    }
+
+    #[test]
+    fn points_at_the_bad_instruction() {
+        let column = |input: &str| {
+            let error = part1(input).err().unwrap();
+            let error = error.downcast_ref::<ParseError>().unwrap().clone();
+            (error.line, error.column)
+        };
+        assert_eq!(column("mem[8] = 11\nmask = 1X0\n"), (Some(2), 8));
+        assert_eq!(
+            column("mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0Y\n"),
+            (Some(1), 43)
+        );
+        assert_eq!(column("mem[8] = 11\nmem[x] = 7\n"), (Some(2), 5));
+        assert_eq!(column("mem[8] = -11\n"), (Some(1), 10));
+        assert_eq!(column("nop\n"), (Some(1), 1));
+    }
+
+    #[test]
+    fn names_the_limit_of_the_floating_bits() {
+        let mask = format!(
+            "{}{}",
+            "X".repeat(MAX_FLOATING_BITS + 1),
+            "0".repeat(35 - MAX_FLOATING_BITS)
+        );
+        let input =
+            format!("mask = {}\nmem[8] = 11\n", "0".repeat(36)) + &format!("mask = {}\n", mask);
+        assert!(part1(&input).is_ok());
+
+        let error = format!("{:#}", part2(&input).err().unwrap());
+        assert!(
+            error.starts_with("Line 3: The mask has 17 floating bits, but at most 16"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn streams_the_same_answers() -> Result<()> {
+        let parts = aoc_common::parts![part1, part2];
+        let stream_parts = aoc_common::stream_parts![stream::part1, stream::part2];
+        let params = Params::default();
+        same_answers(EXAMPLE, parts, stream_parts, &params)?;
+        same_answers(EXAMPLE_2, parts, stream_parts, &params)?;
+        for seed in 0..10 {
+            let input = generate::generate(seed, 300).input;
+            same_answers(&input, parts, stream_parts, &params)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+//! The same parts, reading the initialization program line by line
+//!
+//! Only the memory of the program stays, which is as big as the number of the addresses it writes.
+
+use crate::{get_instruction, Program};
+use anyhow::{Context, Result};
+use aoc_common::stream::Source;
+
+pub fn part1(source: Source<'_>) -> Result<u64> {
+    process(source, false)
+}
+
+pub fn part2(source: Source<'_>) -> Result<u64> {
+    process(source, true)
+}
+
+fn process(source: Source<'_>, floating: bool) -> Result<u64> {
+    let mut program = Program::new(floating);
+    for line in source.lines()? {
+        let (number, line) = line?;
+        program
+            .execute(get_instruction(&line).map_err(|e| e.at_line(number))?)
+            .with_context(|| format!("Line {}", number))?;
+    }
+    program.sum()
+}
@@ -0,0 +1,56 @@
+//! Starting numbers of the memory game
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::seq::SliceRandom;
+use std::ops::RangeInclusive;
+
+/// The number of starting numbers in the real input
+pub const PUZZLE_SCALE: usize = 7;
+
+/// The numbers of starting numbers
+pub const SCALES: RangeInclusive<usize> = 1..=100;
+
+/// Makes `scale` distinct starting numbers, at least one and at most 100
+///
+/// The answers come from a table of the last turn of every number, which is as big as the number of turns.
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    let mut numbers = (0..100).collect::<Vec<u32>>();
+    numbers.shuffle(&mut rng);
+    numbers.truncate(scale.clamp(*SCALES.start(), *SCALES.end()));
+
+    let numbers_text = numbers.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+
+    Generated {
+        input: format!("{}\n", numbers_text.join(",")),
+        answers: vec![
+            Some(Answer::from(spoken(&numbers, 2020))),
+            Some(Answer::from(spoken(&numbers, 30_000_000))),
+        ],
+        params: Params::default(),
+    }
+}
+
+/// The number spoken on the last turn
+fn spoken(starting: &[u32], turns: u32) -> u32 {
+    // Zero means never spoken, so the turns are 1-based
+    let mut last_turn = vec![0_u32; turns as usize];
+    // We may .unwrap() here, because there is at least one starting number
+    let (&first, rest) = starting.split_first().unwrap();
+    let mut current = first;
+
+    for turn in 1..turns {
+        let next = match rest.get(turn as usize - 1) {
+            Some(&n) => n,
+            None => match last_turn[current as usize] {
+                0 => 0,
+                seen => turn - seen,
+            },
+        };
+        last_turn[current as usize] = turn;
+        current = next;
+    }
+
+    current
+}
@@ -0,0 +1,125 @@
+pub mod generate;
+
+use anyhow::Result;
+use aoc_common::parse::parse_token;
+use aoc_common::{Param, Params};
+use std::collections::{hash_map::Entry, HashMap, VecDeque};
+
+const PART1_TURNS: Param = Param {
+    name: "part1-turns",
+    default: "2020",
+    help: "The turn of the number to find in part 1",
+};
+
+const PART2_TURNS: Param = Param {
+    name: "part2-turns",
+    default: "30000000",
+    help: "The turn of the number to find in part 2",
+};
+
+pub const PARAMS: &[Param] = &[PART1_TURNS, PART2_TURNS];
+
+pub fn part1(input: &str) -> Result<u64> {
+    part1_with(input, &Params::default())
+}
+
+pub fn part2(input: &str) -> Result<u64> {
+    part2_with(input, &Params::default())
+}
+
+pub fn part1_with(input: &str, params: &Params) -> Result<u64> {
+    process(input, params.get(&PART1_TURNS)?)
+}
+
+pub fn part2_with(input: &str, params: &Params) -> Result<u64> {
+    process(input, params.get(&PART2_TURNS)?)
+}
+
+pub fn parse(input: &str) -> Result<impl Sized> {
+    get_data(input)
+}
+
+fn get_data(input: &str) -> Result<Vec<u64>> {
+    let line = input.trim_end();
+    let numbers = line
+        .split(',')
+        .map(|s| parse_token(line, s, "a number"))
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.at_line(1))?;
+    Ok(numbers)
+}
+
+fn process(input: &str, max_n: usize) -> Result<u64> {
+    let mut number_iter = get_data(input)?.into_iter();
+
+    let mut history: HashMap<_, VecDeque<_>> = HashMap::new();
+    let mut previous_number = 0;
+    let mut current_number = 0;
+
+    for i in 1..=max_n {
+        // To speed up the execution, we need to remove the match on each iteration
+        // But for now I'm fine with that
+        current_number = match number_iter.next() {
+            Some(n) => n,
+            None => match history.entry(previous_number) {
+                Entry::Occupied(pvd) => {
+                    let pvd = pvd.get();
+                    match pvd.len() {
+                        1 => 0,
+                        // We may call .unwrap() here, because we checked the length above
+                        2 => (*pvd.front().unwrap() - *pvd.get(1).unwrap()) as u64,
+                        // This arm is really unreachable because of lines 50 and 51
+                        _ => unreachable!(),
+                    }
+                }
+                Entry::Vacant(_) => 0,
+            },
+        };
+
+        let cvd = history.entry(current_number).or_insert_with(VecDeque::new);
+        cvd.push_front(i);
+        cvd.truncate(2);
+        previous_number = current_number;
+    }
+
+    Ok(current_number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part1_examples() -> Result<()> {
+        assert_eq!(part1("0,3,6")?, 436);
+        assert_eq!(part1("1,3,2")?, 1);
+        assert_eq!(part1("2,1,3")?, 10);
+        assert_eq!(part1("1,2,3")?, 27);
+        assert_eq!(part1("2,3,1")?, 78);
+        assert_eq!(part1("3,2,1")?, 438);
+        assert_eq!(part1("3,1,2")?, 1836);
+        Ok(())
+    }
+
+    #[test]
+    #[ignore = "30 million turns take too long in debug builds, run with --release -- --ignored"]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2("0,3,6")?, 175594);
+        Ok(())
+    }
+
+    #[test]
+    fn stops_at_the_given_turn() -> Result<()> {
+        // 0, 3, 6, 0, 3, 3, 1, 0, 4, 0
+        let params = Params::default().with(&PART2_TURNS, 9);
+        assert_eq!(part2_with("0,3,6", &params)?, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn points_at_a_bad_number() {
+        let error = part1("0,3,x6\n").err().unwrap();
+        let error = error.downcast_ref::<aoc_common::ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (Some(1), 5));
+    }
+}
@@ -0,0 +1,134 @@
+//! Ticket notes where the fields can be found one by one
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::ops::RangeInclusive;
+
+/// The number of nearby tickets in the real input
+pub const PUZZLE_SCALE: usize = 241;
+
+/// The numbers of nearby tickets
+pub const SCALES: RangeInclusive<usize> = 0..=usize::MAX;
+
+const FIELDS: [&str; 20] = [
+    "departure location",
+    "departure station",
+    "departure platform",
+    "departure track",
+    "departure date",
+    "departure time",
+    "arrival location",
+    "arrival station",
+    "arrival platform",
+    "arrival track",
+    "class",
+    "duration",
+    "price",
+    "route",
+    "row",
+    "seat",
+    "train",
+    "type",
+    "wagon",
+    "zone",
+];
+
+/// No field allows a value above this, so the invalid values are above it
+const MAX_VALID: u32 = 900;
+
+/// Makes `scale` nearby tickets, a fifth of them with one invalid value
+///
+/// The field of rank `r` allows the values up to `40 * r + 140` but one, so the field of rank `r` fits the positions of
+/// the ranks from `r` up. My ticket has a value above the limit of the rank below at every position, so that the
+/// positions can be matched starting from the last rank, as the day does.
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+
+    let limits = (0..FIELDS.len() as u32)
+        .map(|rank| 40 * rank + 140)
+        .collect::<Vec<_>>();
+    let gaps = limits
+        .iter()
+        .map(|&limit| rng.gen_range(2..limit))
+        .collect::<Vec<_>>();
+
+    let mut fields = FIELDS.to_vec();
+    fields.shuffle(&mut rng);
+
+    // The rank of the field at each position
+    let mut ranks = (0..FIELDS.len()).collect::<Vec<_>>();
+    ranks.shuffle(&mut rng);
+
+    let my_ticket = ranks
+        .iter()
+        .map(|&rank| match rank {
+            0 => value(&mut rng, 1, limits[rank], &gaps),
+            _ => value(&mut rng, limits[rank - 1] + 1, limits[rank], &gaps),
+        })
+        .collect::<Vec<_>>();
+
+    let mut nearby_tickets = vec![];
+    let mut error_rate = 0;
+    for _ in 0..scale {
+        let mut ticket = ranks
+            .iter()
+            .map(|&rank| value(&mut rng, 1, limits[rank], &gaps))
+            .collect::<Vec<_>>();
+        if rng.gen_bool(0.2) {
+            let invalid = rng.gen_range(MAX_VALID + 1..1000);
+            ticket[rng.gen_range(0..FIELDS.len())] = invalid;
+            error_rate += invalid;
+        }
+        nearby_tickets.push(ticket);
+    }
+
+    let rules = (0..FIELDS.len())
+        .map(|rank| {
+            format!(
+                "{}: 1-{} or {}-{}\n",
+                fields[rank],
+                gaps[rank] - 1,
+                gaps[rank] + 1,
+                limits[rank]
+            )
+        })
+        .collect::<String>();
+
+    let departure_product = ranks
+        .iter()
+        .zip(&my_ticket)
+        .filter(|&(&rank, _)| fields[rank].starts_with("departure"))
+        .map(|(_, &value)| u64::from(value))
+        .product::<u64>();
+
+    let ticket_line = |ticket: &Vec<u32>| {
+        let values = ticket.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        format!("{}\n", values.join(","))
+    };
+
+    Generated {
+        input: format!(
+            "{}\nyour ticket:\n{}\nnearby tickets:\n{}",
+            rules,
+            ticket_line(&my_ticket),
+            nearby_tickets.iter().map(ticket_line).collect::<String>()
+        ),
+        answers: vec![
+            Some(Answer::from(error_rate)),
+            Some(Answer::from(departure_product)),
+        ],
+        params: Params::default(),
+    }
+}
+
+/// Any value in the range but the gaps, so it fits every field with a big enough limit
+fn value(rng: &mut impl Rng, min: u32, max: u32, gaps: &[u32]) -> u32 {
+    loop {
+        let value = rng.gen_range(min..=max);
+        if !gaps.contains(&value) {
+            return value;
+        }
+    }
+}
@@ -0,0 +1,251 @@
+pub mod generate;
+
+use anyhow::{bail, Result};
+use aoc_common::parse::{parse_lines, parse_token, split_numbered_groups, OrInvalid};
+use aoc_common::ParseError;
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use tracing::debug;
+
+pub fn part1(input: &str) -> Result<u32> {
+    let (named_ranges, _, nearby_tickets) = get_data(input)?;
+
+    Ok(nearby_tickets
+        .into_iter()
+        .flatten()
+        .filter(|v| {
+            !named_ranges
+                .iter()
+                .any(|nr| nr.ranges[0].contains(v) || nr.ranges[1].contains(v))
+        })
+        .sum())
+}
+
+pub fn part2(input: &str) -> Result<u64> {
+    let (named_ranges, my_ticket, nearby_tickets) = get_data(input)?;
+
+    let transcript = find_transcript(&named_ranges, &my_ticket, nearby_tickets)?;
+
+    Ok(transcript
+        .into_iter()
+        .filter(|(k, _)| k.starts_with("departure"))
+        .map(|(_, v)| v)
+        // We may use the index, because the transcript only has the indexes of my ticket fields
+        .map(|i| my_ticket[i] as u64)
+        .product())
+}
+
+pub fn parse(input: &str) -> Result<impl Sized> {
+    get_data(input)
+}
+
+/// Finds the index of each field on the tickets
+fn find_transcript(
+    named_ranges: &[NamedRange],
+    my_ticket: &[u32],
+    nearby_tickets: Vec<Vec<u32>>,
+) -> Result<HashMap<String, usize>> {
+    let field_count = my_ticket.len();
+
+    let good_tickets = nearby_tickets
+        .into_iter()
+        .filter(|vc| {
+            vc.iter().all(|v| {
+                named_ranges
+                    .iter()
+                    .any(|nr| nr.ranges[0].contains(v) || nr.ranges[1].contains(v))
+            })
+        })
+        .chain(std::iter::once(my_ticket.to_vec()))
+        .collect::<Vec<_>>();
+    debug!(good = good_tickets.len(), "tickets without invalid values");
+
+    let mut possible_transcripts: HashMap<usize, HashSet<String>> = HashMap::new();
+
+    for i in 0..field_count {
+        for range in named_ranges.iter() {
+            let found = good_tickets
+                .iter()
+                // get_data has checked that all the tickets have the fields of mine, so we may use the index
+                .all(|t| range.ranges[0].contains(&t[i]) || range.ranges[1].contains(&t[i]));
+
+            if found {
+                let v = possible_transcripts.entry(i).or_default();
+                v.insert(range.name.clone());
+            }
+        }
+    }
+
+    let mut possible_transcripts = possible_transcripts.into_iter().collect::<Vec<_>>();
+
+    possible_transcripts.sort_unstable_by_key(|x| x.1.len());
+
+    let mut transcript: HashMap<String, usize> = HashMap::new();
+    let mut found: HashSet<String> = HashSet::new();
+
+    for (ix, current_hs) in possible_transcripts.into_iter() {
+        let diff = current_hs.difference(&found).cloned().collect::<Vec<_>>();
+        debug!(
+            index = ix,
+            candidates = current_hs.len(),
+            left = ?diff,
+            "eliminated the found fields"
+        );
+
+        if diff.len() != 1 {
+            bail!("Can't find transcript");
+        }
+
+        let diff = diff.into_iter().next().or_invalid()?;
+        transcript.insert(diff.clone(), ix);
+        found.insert(diff);
+    }
+
+    Ok(transcript)
+}
+
+#[derive(Debug)]
+struct NamedRange {
+    name: String,
+    ranges: [RangeInclusive<u32>; 2],
+}
+
+impl FromStr for NamedRange {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut splitted = value.split(':');
+        // We may .unwrap() here, because split always gives at least one item
+        let name = splitted.next().unwrap();
+        let mut ranges_iter = splitted
+            .next()
+            .ok_or_else(|| ParseError::after(value, name, "\":\""))?
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|s| !s.is_empty())
+            .map(|s| parse_token(value, s, "a number"));
+        let mut next_bound = || {
+            ranges_iter
+                .next()
+                .ok_or_else(|| ParseError::after(value, value, "a range like \"1-3\""))?
+        };
+        Ok(NamedRange {
+            name: name.to_string(),
+            ranges: [
+                RangeInclusive::new(next_bound()?, next_bound()?),
+                RangeInclusive::new(next_bound()?, next_bound()?),
+            ],
+        })
+    }
+}
+
+type Data = Result<(Vec<NamedRange>, Vec<u32>, Vec<Vec<u32>>)>;
+
+fn get_data(input: &str) -> Data {
+    let mut input_iter = split_numbered_groups(input);
+
+    // An empty input has no groups at all
+    let missing = |expected| ParseError::in_block(input, &input[input.len()..], expected);
+
+    let (start, named_ranges) = input_iter
+        .next()
+        .ok_or_else(|| missing("the rules of the fields"))?;
+    let named_ranges =
+        parse_lines(named_ranges, NamedRange::from_str).map_err(|e| e.at_line(start))?;
+
+    let parse_ticket = |(line_n, line): (usize, &str)| {
+        line.split(',')
+            .map(|t| parse_token(line, t, "a number"))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.at_line(line_n))
+    };
+
+    let my_ticket = get_tickets(&mut input_iter)
+        .map(parse_ticket)
+        .next()
+        .ok_or_else(|| missing("your ticket"))??;
+
+    let nearby_tickets = get_tickets(&mut input_iter)
+        .map(|(line_n, line)| {
+            let ticket = parse_ticket((line_n, line))?;
+            match ticket.len() == my_ticket.len() {
+                true => Ok(ticket),
+                false => Err(ParseError::new(
+                    line,
+                    line,
+                    format!("{} values like your ticket", my_ticket.len()),
+                )
+                .at_line(line_n)),
+            }
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok((named_ranges, my_ticket, nearby_tickets))
+}
+
+fn get_tickets<'a>(
+    input_iter: &mut impl Iterator<Item = (usize, &'a str)>,
+) -> impl Iterator<Item = (usize, &'a str)> {
+    // We just substitute an empty str, if the input is bad
+    let (start, group) = input_iter.next().unwrap_or_default();
+    group
+        .lines()
+        .enumerate()
+        .skip(1)
+        .map(move |(ix, line)| (start + ix, line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("../input/input.txt");
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+    const EXAMPLE_2: &str = include_str!("../input/example2.txt");
+
+    #[test]
+    fn same_answers_for_lf_and_crlf() -> Result<()> {
+        let lf = INPUT.replace("\r\n", "\n");
+        let crlf = lf.replace('\n', "\r\n");
+
+        assert_eq!(part1(&lf)?, part1(&crlf)?);
+        assert_eq!(part2(&lf)?, part2(&crlf)?);
+        Ok(())
+    }
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(EXAMPLE)?, 71);
+        Ok(())
+    }
+
+    #[test]
+    fn finds_the_transcript_of_the_example() -> Result<()> {
+        let (named_ranges, my_ticket, nearby_tickets) = get_data(EXAMPLE_2)?;
+        let transcript = find_transcript(&named_ranges, &my_ticket, nearby_tickets)?;
+        assert_eq!(transcript["row"], 0);
+        assert_eq!(transcript["class"], 1);
+        assert_eq!(transcript["seat"], 2);
+        Ok(())
+    }
+
+    #[test]
+    fn parses_a_named_range() -> Result<()> {
+        let named_range = NamedRange::from_str("departure location: 1-3 or 5-7")?;
+        assert_eq!(named_range.name, "departure location");
+        assert_eq!(named_range.ranges, [1..=3, 5..=7]);
+        Ok(())
+    }
+
+    #[test]
+    fn points_at_the_short_ticket() {
+        let input = EXAMPLE.replace("40,4,50", "40,4");
+        let error = get_data(&input)
+            .err()
+            .and_then(|e| e.downcast::<ParseError>().ok());
+        assert_eq!(
+            error.map(|e| (e.line, e.column, e.expected)),
+            Some((Some(10), 1, "3 values like your ticket".to_string()))
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! Initial slices of the pocket dimension of any size
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
+
+/// The side of the initial slice in the real input
+pub const PUZZLE_SCALE: usize = 8;
+
+/// The sides of the initial slices
+pub const SCALES: RangeInclusive<usize> = 1..=usize::MAX;
+
+/// Makes an initial slice of `scale` by `scale` cubes, at least one
+///
+/// The answers count the active neighbors of every cube next to an active one, so only the active region is visited.
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    let scale = scale.max(*SCALES.start());
+    let active = (0..scale)
+        .map(|_| (0..scale).map(|_| rng.gen_bool(0.45)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let mut input = String::new();
+    for row in &active {
+        input.extend(row.iter().map(|&a| if a { '#' } else { '.' }));
+        input.push('\n');
+    }
+
+    let cubes = active
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|&(_, &a)| a)
+                .map(move |(x, _)| [x as i32, y as i32, 0, 0])
+        })
+        .collect::<HashSet<_>>();
+
+    Generated {
+        input,
+        answers: vec![
+            Some(Answer::from(boot(cubes.clone(), 3))),
+            Some(Answer::from(boot(cubes, 4))),
+        ],
+        params: Params::default(),
+    }
+}
+
+/// Runs the six cycles in the first `dimensions` dimensions and counts the active cubes
+fn boot(mut cubes: HashSet<[i32; 4]>, dimensions: usize) -> usize {
+    let offsets = (0..3_i32.pow(dimensions as u32))
+        .map(|n| {
+            let mut offset = [0; 4];
+            for (d, o) in offset.iter_mut().enumerate().take(dimensions) {
+                *o = n / 3_i32.pow(d as u32) % 3 - 1;
+            }
+            offset
+        })
+        .filter(|o| o != &[0; 4])
+        .collect::<Vec<_>>();
+
+    for _ in 0..6 {
+        let mut neighbors = HashMap::<[i32; 4], usize>::new();
+        for cube in &cubes {
+            for offset in &offsets {
+                let mut neighbor = *cube;
+                for d in 0..4 {
+                    neighbor[d] += offset[d];
+                }
+                *neighbors.entry(neighbor).or_default() += 1;
+            }
+        }
+
+        cubes = neighbors
+            .into_iter()
+            .filter(|(cube, count)| *count == 3 || (*count == 2 && cubes.contains(cube)))
+            .map(|(cube, _)| cube)
+            .collect();
+    }
+
+    cubes.len()
+}
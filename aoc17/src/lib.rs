@@ -0,0 +1,105 @@
+//! Generic over dimension solution
+//! Requires stable Rust 1.51+
+
+pub mod generate;
+
+use anyhow::{bail, Result};
+use aoc_common::automaton::{Automaton, Lattice, Moore, Rule};
+use aoc_common::grid::Grid;
+use aoc_common::{Param, Params};
+
+const CYCLES: Param = Param {
+    name: "cycles",
+    default: "6",
+    help: "The number of the boot cycles",
+};
+
+pub const PARAMS: &[Param] = &[CYCLES];
+
+pub fn part1(input: &str) -> Result<usize> {
+    part1_with(input, &Params::default())
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    part2_with(input, &Params::default())
+}
+
+pub fn part1_with(input: &str, params: &Params) -> Result<usize> {
+    process::<3>(input, params.get(&CYCLES)?)
+}
+
+pub fn part2_with(input: &str, params: &Params) -> Result<usize> {
+    process::<4>(input, params.get(&CYCLES)?)
+}
+
+pub fn parse(input: &str) -> Result<impl Sized> {
+    get_cubes::<3>(input)
+}
+
+fn process<const DIM: usize>(input: &str, cycles: usize) -> Result<usize> {
+    let cubes = get_cubes::<DIM>(input)?;
+
+    // An active cube stays active with 2 or 3 active neighbors, an inactive one becomes active with exactly 3
+    let mut world = Automaton::new(Lattice::<DIM>, Moore, Rule::new(&[3], &[2, 3]), cubes);
+    world.steps(cycles);
+
+    Ok(world.live_count())
+}
+
+/// The active cubes of the initial slice
+fn get_cubes<const DIM: usize>(input: &str) -> Result<Vec<[i32; DIM]>> {
+    if DIM < 2 {
+        bail!("There must be at least 2 dimensions");
+    }
+
+    let slice = Grid::parse(
+        input,
+        |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        },
+        "an active or an inactive cube",
+    )?;
+
+    let cubes = slice
+        .positions()
+        .filter(|&pos| slice[pos])
+        .map(|(y, x)| {
+            let mut values = [0; DIM];
+            // We may use the index because we checked the length above
+            values[0] = x as i32;
+            values[1] = y as i32;
+            values
+        })
+        .collect();
+
+    Ok(cubes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(EXAMPLE)?, 112);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(EXAMPLE)?, 848);
+        Ok(())
+    }
+
+    #[test]
+    fn one_cycle_of_the_example() -> Result<()> {
+        let params = Params::default().with(&CYCLES, 1);
+        assert_eq!(part1_with(EXAMPLE, &params)?, 11);
+        assert_eq!(part2_with(EXAMPLE, &params)?, 29);
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+//! Homework of random expressions with nested parentheses
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::Rng;
+use std::ops::RangeInclusive;
+
+/// The number of expressions in the real input
+pub const PUZZLE_SCALE: usize = 375;
+
+/// The numbers of expressions
+pub const SCALES: RangeInclusive<usize> = 0..=usize::MAX;
+
+/// The results stay below this, so that the sums of the results fit `u64` too
+const MAX_RESULT: u64 = 1 << 48;
+
+/// Makes `scale` expressions, nested at most three levels deep
+///
+/// The generator keeps the expressions as trees, so the answers come without any parsing.
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    let mut input = String::new();
+    let (mut part1, mut part2) = (0, 0);
+
+    for _ in 0..scale {
+        let (expression, left_to_right, addition_first) = loop {
+            let expression = Expression::random(&mut rng, 0);
+            let results = (
+                expression.eval(false).filter(|&r| r < MAX_RESULT),
+                expression.eval(true).filter(|&r| r < MAX_RESULT),
+            );
+            if let (Some(left_to_right), Some(addition_first)) = results {
+                break (expression, left_to_right, addition_first);
+            }
+        };
+
+        input.push_str(&expression.to_string());
+        input.push('\n');
+        part1 += left_to_right;
+        part2 += addition_first;
+    }
+
+    Generated {
+        input,
+        answers: vec![Some(Answer::from(part1)), Some(Answer::from(part2))],
+        params: Params::default(),
+    }
+}
+
+enum Term {
+    Number(u64),
+    Parentheses(Expression),
+}
+
+/// The first term and the rest of the terms with the operators before them, `true` for `+`
+struct Expression {
+    first: Box<Term>,
+    rest: Vec<(bool, Term)>,
+}
+
+impl Term {
+    fn random(rng: &mut impl Rng, depth: usize) -> Self {
+        match depth < 3 && rng.gen_bool(0.25) {
+            true => Term::Parentheses(Expression::random(rng, depth + 1)),
+            false => Term::Number(rng.gen_range(1..=9)),
+        }
+    }
+}
+
+impl Expression {
+    fn random(rng: &mut impl Rng, depth: usize) -> Self {
+        Expression {
+            first: Box::new(Term::random(rng, depth)),
+            rest: (0..rng.gen_range(1..=5))
+                .map(|_| (rng.gen_bool(0.5), Term::random(rng, depth)))
+                .collect(),
+        }
+    }
+
+    /// Evaluates the expression left to right, or the additions first, `None` if it overflows
+    fn eval(&self, addition_first: bool) -> Option<u64> {
+        let term = |t: &Term| match t {
+            Term::Number(n) => Some(*n),
+            Term::Parentheses(e) => e.eval(addition_first),
+        };
+
+        let mut result = term(&self.first)?;
+        match addition_first {
+            false => {
+                for (add, t) in &self.rest {
+                    result = match add {
+                        true => result.checked_add(term(t)?)?,
+                        false => result.checked_mul(term(t)?)?,
+                    };
+                }
+                Some(result)
+            }
+            // The sum so far waits for the next multiplication
+            true => {
+                let mut product = 1_u64;
+                for (add, t) in &self.rest {
+                    match add {
+                        true => result = result.checked_add(term(t)?)?,
+                        false => {
+                            product = product.checked_mul(result)?;
+                            result = term(t)?;
+                        }
+                    }
+                }
+                product.checked_mul(result)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Term {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Term::Number(n) => write!(f, "{}", n),
+            Term::Parentheses(e) => write!(f, "({})", e),
+        }
+    }
+}
+
+impl std::fmt::Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.first)?;
+        for (add, term) in &self.rest {
+            write!(f, " {} {}", if *add { '+' } else { '*' }, term)?;
+        }
+        Ok(())
+    }
+}
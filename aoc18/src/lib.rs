@@ -0,0 +1,304 @@
+//! This solution definitely requires some refactoring :C
+
+#[macro_use]
+extern crate lazy_static;
+
+pub mod generate;
+pub mod stream;
+
+use anyhow::{anyhow, bail, Result};
+use aoc_common::parse::parse_token;
+use aoc_common::ParseError;
+use regex::{Captures, Regex};
+
+lazy_static! {
+    static ref RE_PAR: Regex = Regex::new(r"\([0-9*\+ ]+\)").unwrap();
+    static ref RE_ADD: Regex = Regex::new(r"\d+ \+ \d+").unwrap();
+    static ref RE_PAR_REM: Regex = Regex::new(r"\(\d+\)").unwrap();
+}
+
+pub fn part1(input: &str) -> Result<u64> {
+    process(input, eval_process_part1)
+}
+
+pub fn part2(input: &str) -> Result<u64> {
+    process(input, eval_process_part2)
+}
+
+// The expressions are evaluated by rewriting the lines, so we only check them up front
+pub fn parse(input: &str) -> Result<impl Sized + '_> {
+    let lines = input
+        .lines()
+        .enumerate()
+        .map(|(ix, line)| check(line).map(|()| line).map_err(|e| e.at_line(ix + 1)))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(lines)
+}
+
+fn process(input: &str, eval_fn: impl Fn(&str) -> Result<u64>) -> Result<u64> {
+    let mut sum = 0_u64;
+    for (ix, line) in input.lines().enumerate() {
+        check(line).map_err(|e| e.at_line(ix + 1))?;
+        sum = sum
+            .checked_add(eval_fn(line)?)
+            .ok_or_else(|| anyhow!("The sum of the results is too big"))?;
+    }
+    Ok(sum)
+}
+
+/// Checks that the line is made of the numbers, `+`, `*` and the parentheses in the right order
+///
+/// The evaluation rewrites the line and loses the columns, so we point at the bad token before it starts.
+fn check(line: &str) -> Result<(), ParseError> {
+    let mut depth = 0_usize;
+    let mut operand = true;
+    let mut ix = 0;
+
+    while let Some(c) = line[ix..].chars().next() {
+        let len = match c {
+            '0'..='9' => line[ix..]
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(line.len() - ix),
+            _ => c.len_utf8(),
+        };
+        let token = &line[ix..ix + len];
+
+        match c {
+            ' ' => {}
+            '0'..='9' if operand => {
+                parse_token::<u64>(line, token, "a number")?;
+                operand = false;
+            }
+            '(' if operand => depth += 1,
+            '+' | '*' if !operand => operand = true,
+            ')' if !operand && depth > 0 => depth -= 1,
+            _ => {
+                let expected = match (operand, depth) {
+                    (true, _) => "a number or (",
+                    (false, 0) => "+ or *",
+                    (false, _) => "+, * or )",
+                };
+                return Err(ParseError::new(line, token, expected));
+            }
+        }
+        ix += len;
+    }
+
+    match (operand, depth) {
+        (true, _) => Err(ParseError::after(line, line, "a number or (")),
+        (false, 0) => Ok(()),
+        (false, _) => Err(ParseError::after(line, line, ")")),
+    }
+}
+
+fn eval_process_part1(line: &str) -> Result<u64> {
+    let mut line = line.to_string();
+    while has_parentheses(line.as_str()) {
+        line = eval_inside_parentheses(line);
+    }
+    eval_default(&line, false)
+}
+
+fn eval_process_part2(line: &str) -> Result<u64> {
+    let mut line = line.to_string();
+    while has_parentheses(line.as_str()) {
+        while has_additions(line.as_str()) {
+            line = eval_additions(line);
+            while has_unnecessary_parentheses(line.as_str()) {
+                line = remove_unnecessary_parentheses(line);
+            }
+        }
+        line = eval_inside_parentheses(line);
+    }
+    while has_additions(line.as_str()) {
+        line = eval_additions(line);
+    }
+    eval_default(&line, false)
+}
+
+fn has_parentheses(line: &str) -> bool {
+    RE_PAR.is_match(line)
+}
+
+fn eval_inside_parentheses(line: String) -> String {
+    // Todo: find a way to properly fail .replace_all() and then replace .unwrap_or_default()
+    RE_PAR
+        .replace_all(line.as_str(), |c: &Captures| {
+            eval_default(&c[0], true).unwrap_or_default().to_string()
+        })
+        .to_string()
+}
+
+fn has_additions(line: &str) -> bool {
+    RE_ADD.is_match(line)
+}
+
+fn eval_additions(line: String) -> String {
+    // Todo: find a way to properly fail .replace_all() and then replace .unwrap_or_default()
+    RE_ADD
+        .replace_all(line.as_str(), |c: &Captures| {
+            eval_one_addition(&c[0]).unwrap_or_default().to_string()
+        })
+        .to_string()
+}
+
+fn has_unnecessary_parentheses(line: &str) -> bool {
+    RE_PAR_REM.is_match(line)
+}
+
+fn remove_unnecessary_parentheses(line: String) -> String {
+    RE_PAR_REM
+        .replace_all(line.as_str(), |c: &Captures| {
+            c[0].trim_matches(|c| c == '(' || c == ')').to_string()
+        })
+        .to_string()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Operation {
+    Add,
+    Mul,
+}
+
+fn eval_default(expr: &str, trim: bool) -> Result<u64> {
+    let expr = match trim {
+        true => expr
+            .strip_prefix('(')
+            .and_then(|e| e.strip_suffix(')'))
+            .ok_or_else(|| anyhow!("Expected parentheses around {:?}", expr))?,
+        false => expr,
+    };
+
+    let result: Result<_> =
+        expr.split_ascii_whitespace()
+            .enumerate()
+            .try_fold((0_u64, None), |mut acc, n| {
+                match n.0 & 1 == 0 {
+                    // Number
+                    true => {
+                        let n = n.1.parse()?;
+                        match acc.1 {
+                            Some(op) => match op {
+                                Operation::Add => {
+                                    acc = (acc.0.checked_add(n).ok_or_else(too_big)?, None);
+                                }
+                                Operation::Mul => {
+                                    acc = (acc.0.checked_mul(n).ok_or_else(too_big)?, None);
+                                }
+                            },
+                            None => {
+                                acc = (n, None);
+                            }
+                        }
+                    }
+                    // Operation
+                    false => {
+                        let op = match n.1 {
+                            "+" => Operation::Add,
+                            "*" => Operation::Mul,
+                            _ => bail!("Expected + or *, found {:?}", n.1),
+                        };
+                        acc = (acc.0, Some(op));
+                    }
+                }
+                Ok(acc)
+            });
+
+    Ok(result?.0)
+}
+
+fn eval_one_addition(expr: &str) -> Result<u64> {
+    let mut expr_iter = expr.split_ascii_whitespace();
+    match (expr_iter.next(), expr_iter.next(), expr_iter.next()) {
+        (Some(first), Some("+"), Some(second)) => first
+            .parse::<u64>()?
+            .checked_add(second.parse::<u64>()?)
+            .ok_or_else(too_big),
+        _ => bail!("Expected an addition, found {:?}", expr),
+    }
+}
+
+fn too_big() -> anyhow::Error {
+    anyhow!("The result is too big")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::stream::same_answers;
+    use aoc_common::Params;
+
+    const EXAMPLES: &[(&str, u64, u64)] = &[
+        ("1 + 2 * 3 + 4 * 5 + 6", 71, 231),
+        ("1 + (2 * 3) + (4 * (5 + 6))", 51, 51),
+        ("2 * 3 + (4 * 5)", 26, 46),
+        ("5 + (8 * 3 + 9 + 3 * 4 * 3)", 437, 1445),
+        ("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", 12240, 669060),
+        (
+            "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2",
+            13632,
+            23340,
+        ),
+    ];
+
+    #[test]
+    fn part1_examples() -> Result<()> {
+        for &(expr, expected, _) in EXAMPLES {
+            assert_eq!(part1(expr)?, expected, "{}", expr);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn part2_examples() -> Result<()> {
+        for &(expr, _, expected) in EXAMPLES {
+            assert_eq!(part2(expr)?, expected, "{}", expr);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn sums_the_lines() -> Result<()> {
+        let input = EXAMPLES.iter().map(|e| e.0).collect::<Vec<_>>().join("\n");
+        assert_eq!(part1(&input)?, EXAMPLES.iter().map(|e| e.1).sum::<u64>());
+        Ok(())
+    }
+
+    #[test]
+    fn points_at_the_bad_token() {
+        let position = |input| {
+            let error = part1(input).err().unwrap();
+            let error = error.downcast_ref::<ParseError>().unwrap().clone();
+            (error.line, error.column, error.expected)
+        };
+        assert_eq!(
+            position("1 + 2\n1 + x"),
+            (Some(2), 5, "a number or (".to_string())
+        );
+        assert_eq!(position("(1 + 2))"), (Some(1), 8, "+ or *".to_string()));
+        assert_eq!(position("(1 + (2 * 3)"), (Some(1), 13, ")".to_string()));
+        assert_eq!(
+            position("2 * * 3"),
+            (Some(1), 5, "a number or (".to_string())
+        );
+        assert_eq!(position("1 + 2 3"), (Some(1), 7, "+ or *".to_string()));
+        assert_eq!(
+            position("99999999999999999999 + 1"),
+            (Some(1), 1, "a number".to_string())
+        );
+    }
+
+    #[test]
+    fn streams_the_same_answers() -> Result<()> {
+        let parts = aoc_common::parts![part1, part2];
+        let stream_parts = aoc_common::stream_parts![stream::part1, stream::part2];
+        let params = Params::default();
+        let example = EXAMPLES.iter().map(|e| e.0).collect::<Vec<_>>().join("\n");
+        same_answers(&example, parts, stream_parts, &params)?;
+        for seed in 0..10 {
+            let input = generate::generate(seed, 300).input;
+            same_answers(&input, parts, stream_parts, &params)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+//! The same parts, reading the expressions line by line
+
+use crate::{check, eval_process_part1, eval_process_part2};
+use anyhow::{anyhow, Result};
+use aoc_common::stream::Source;
+
+pub fn part1(source: Source<'_>) -> Result<u64> {
+    process(source, eval_process_part1)
+}
+
+pub fn part2(source: Source<'_>) -> Result<u64> {
+    process(source, eval_process_part2)
+}
+
+fn process(source: Source<'_>, eval_fn: impl Fn(&str) -> Result<u64>) -> Result<u64> {
+    let mut sum = 0_u64;
+    for line in source.lines()? {
+        let (number, line) = line?;
+        check(&line).map_err(|e| e.at_line(number))?;
+        sum = sum
+            .checked_add(eval_fn(&line)?)
+            .ok_or_else(|| anyhow!("The sum of the results is too big"))?;
+    }
+    Ok(sum)
+}
@@ -0,0 +1,211 @@
+//! Rule sets with a known language: rule 42 and rule 31 match two disjoint sets of words of the same length
+//!
+//! The rule 0 matches two words of rule 42 and one of rule 31, like the real rules.
+//! With the loops of the second part it matches `n` words of rule 42 and then `m` words of rule 31, with `n > m >= 1`.
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+/// The number of messages in the real input
+pub const PUZZLE_SCALE: usize = 400;
+
+/// The numbers of messages
+pub const SCALES: RangeInclusive<usize> = 1..=usize::MAX;
+
+/// The length of the words of rules 42 and 31, the real ones are 8 long
+const WORD_LENGTH: usize = 5;
+
+/// The numbers of the rules that the puzzle itself refers to
+const RESERVED: [usize; 5] = [0, 8, 11, 31, 42];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Word {
+    Rule42,
+    Rule31,
+    Neither,
+}
+
+/// Makes rules for random sets of words and `scale` messages, at least one, some of them valid and some not
+///
+/// The messages are checked by cutting them into words, so the answers don't depend on any rule matching.
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    let scale = scale.max(*SCALES.start());
+
+    let (words, kinds) = loop {
+        let words = (0..1 << WORD_LENGTH)
+            .map(|n: usize| {
+                (0..WORD_LENGTH)
+                    .map(|bit| if n >> bit & 1 == 1 { 'b' } else { 'a' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        let kinds = words
+            .iter()
+            .map(|_| match rng.gen_range(0..5) {
+                0 | 1 => Word::Rule42,
+                2 | 3 => Word::Rule31,
+                _ => Word::Neither,
+            })
+            .collect::<Vec<_>>();
+        if kinds.contains(&Word::Rule42) && kinds.contains(&Word::Rule31) {
+            break (words, kinds);
+        }
+    };
+    let words_of = |kind: Word| {
+        words
+            .iter()
+            .zip(&kinds)
+            .filter(|&(_, &k)| k == kind)
+            .map(|(w, _)| w.clone())
+            .collect::<Vec<_>>()
+    };
+
+    let mut rules = Rules::default();
+    rules.add(vec!["8 11".to_string()], Some(0));
+    rules.add(vec!["42".to_string()], Some(8));
+    rules.add(vec!["42 31".to_string()], Some(11));
+    rules.add_words(&words_of(Word::Rule42), Some(42));
+    rules.add_words(&words_of(Word::Rule31), Some(31));
+
+    let mut messages = vec![];
+    let (mut part1, mut part2) = (0, 0);
+    for _ in 0..scale {
+        let mut message = vec![];
+        let rule_42 = rng.gen_range(1..=5);
+        let rule_31 = rng.gen_range(0..=4);
+        for (count, kind) in [(rule_42, Word::Rule42), (rule_31, Word::Rule31)] {
+            let words = words_of(kind);
+            for _ in 0..count {
+                // We may .unwrap() here, because both kinds have words
+                message.push(words.choose(&mut rng).unwrap().clone());
+            }
+        }
+
+        // Some messages get broken on purpose
+        match rng.gen_range(0..6) {
+            0 => message.shuffle(&mut rng),
+            1 => {
+                let ix = rng.gen_range(0..message.len());
+                // We may .unwrap() here, because there are words of every kind
+                message[ix] = words.choose(&mut rng).unwrap().clone();
+            }
+            2 => message.push(if rng.gen_bool(0.5) { "a" } else { "b" }.to_string()),
+            _ => {}
+        }
+
+        let message = message.concat();
+
+        match split_words(&message, &words, &kinds) {
+            Some((2, 1)) => {
+                part1 += 1;
+                part2 += 1;
+            }
+            Some((rule_42, rule_31)) if rule_42 > rule_31 && rule_31 >= 1 => part2 += 1,
+            _ => {}
+        }
+        messages.push(format!("{}\n", message));
+    }
+
+    let mut lines = rules.lines;
+    lines.shuffle(&mut rng);
+
+    Generated {
+        input: format!("{}\n{}", lines.concat(), messages.concat()),
+        answers: vec![Some(Answer::from(part1)), Some(Answer::from(part2))],
+        params: Params::default(),
+    }
+}
+
+/// The numbers of the words of rule 42 and then of rule 31, if the message is made of them in this order
+fn split_words(message: &str, words: &[String], kinds: &[Word]) -> Option<(usize, usize)> {
+    let word_count = message.len() / WORD_LENGTH;
+    if word_count * WORD_LENGTH != message.len() {
+        return None;
+    }
+
+    let kinds = (0..word_count)
+        .map(|ix| {
+            let word = &message[ix * WORD_LENGTH..(ix + 1) * WORD_LENGTH];
+            // We may .unwrap() here, because every word of the length is in the list
+            kinds[words.iter().position(|w| w == word).unwrap()]
+        })
+        .collect::<Vec<_>>();
+
+    let rule_42 = kinds.iter().take_while(|&&k| k == Word::Rule42).count();
+    let rule_31 = kinds[rule_42..]
+        .iter()
+        .take_while(|&&k| k == Word::Rule31)
+        .count();
+
+    match rule_42 + rule_31 == kinds.len() {
+        true => Some((rule_42, rule_31)),
+        false => None,
+    }
+}
+
+/// The rule lines with the rule for each set of words, so that the same suffixes share their rules
+#[derive(Default)]
+struct Rules {
+    lines: Vec<String>,
+    numbers: HashMap<Vec<String>, usize>,
+    next_number: usize,
+}
+
+impl Rules {
+    /// Adds a rule with the given alternatives under the given number, or the next free one
+    fn add(&mut self, alternatives: Vec<String>, number: Option<usize>) -> usize {
+        let number = number.unwrap_or_else(|| {
+            while RESERVED.contains(&self.next_number) {
+                self.next_number += 1;
+            }
+            self.next_number += 1;
+            self.next_number - 1
+        });
+        self.lines
+            .push(format!("{}: {}\n", number, alternatives.join(" | ")));
+        number
+    }
+
+    /// Adds the rule that matches exactly the given words of the same length, one alternative per first letter
+    fn add_words(&mut self, words: &[String], number: Option<usize>) -> usize {
+        if let Some(&known) = self.numbers.get(words) {
+            return known;
+        }
+
+        let rule = match words {
+            [word] if word.len() == 1 => self.add(vec![format!("\"{}\"", word)], number),
+            _ => {
+                let mut alternatives = vec![];
+                for first in ["a", "b"] {
+                    let with_first = words
+                        .iter()
+                        .filter(|w| w.starts_with(first))
+                        .collect::<Vec<_>>();
+                    if with_first.is_empty() {
+                        continue;
+                    }
+
+                    let letter = self.add_words(&[first.to_string()], None);
+                    let rest = with_first
+                        .iter()
+                        .map(|w| w[1..].to_string())
+                        .filter(|w| !w.is_empty())
+                        .collect::<Vec<_>>();
+                    alternatives.push(match rest.is_empty() {
+                        true => letter.to_string(),
+                        false => format!("{} {}", letter, self.add_words(&rest, None)),
+                    });
+                }
+                self.add(alternatives, number)
+            }
+        };
+
+        self.numbers.insert(words.to_vec(), rule);
+        rule
+    }
+}
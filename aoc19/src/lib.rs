@@ -0,0 +1,351 @@
+//! Inspired by
+//! https://dev.to/qviper/advent-of-code-2020-python-solution-day-19-4p9d
+
+pub mod generate;
+
+use anyhow::{bail, Context, Result};
+use aoc_common::parse::{parse_lines, parse_token, split_numbered_groups};
+use aoc_common::ParseError;
+use std::collections::HashSet;
+use std::str::FromStr;
+
+/// The longest message of the puzzle input takes about 6000 steps, while the ambiguous rules may take exponentially many
+const MAX_STEPS: usize = 100_000;
+
+pub fn part1(input: &str) -> Result<usize> {
+    process(input, false)
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    process(input, true)
+}
+
+pub fn parse(input: &str) -> Result<impl Sized + '_> {
+    get_data(input)
+}
+
+fn process(input: &str, part2: bool) -> Result<usize> {
+    let (mut rules, messages) = get_data(input)?;
+
+    if part2 {
+        // The new rules refer to rules 42 and 31, so they must be there
+        if rules.len() <= 42 {
+            bail!(
+                "Part 2 loops rules 8 and 11 through rule 42, but the last rule is {}",
+                rules.len() - 1
+            );
+        }
+        rules[8] = Rule::Multi(vec![vec![42], vec![42, 8]]);
+        rules[11] = Rule::Multi(vec![vec![42, 31], vec![42, 11, 31]]);
+    }
+
+    let rules = rules;
+
+    if let Some(number) = looping_rule(&rules) {
+        bail!(
+            "Rule {} turns into itself without matching anything",
+            number
+        );
+    }
+
+    // The gaps are the only rules without alternatives, because an empty alternative doesn't parse
+    // We may use the index here, because get_data gives at least one rule
+    if rules[0] == Rule::Multi(vec![]) {
+        bail!("There is no rule 0 to match the messages against");
+    }
+
+    let mut count = 0;
+    for (line, message) in messages {
+        let matched = matches(message, &rules, vec![StackedValue::Usize(0)], &mut 0)
+            .with_context(|| format!("Can't match the message on line {}", line))?;
+        if matched {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// The rules at their numbers and the messages with their line numbers
+type Data<'a> = Result<(Vec<Rule>, Vec<(usize, &'a str)>)>;
+
+fn get_data(input: &str) -> Data<'_> {
+    // The matching steps over the messages byte by byte
+    for (ix, line) in input.lines().enumerate() {
+        if let Some((offset, c)) = line.char_indices().find(|(_, c)| !c.is_ascii()) {
+            let token = &line[offset..offset + c.len_utf8()];
+            return Err(ParseError::new(line, token, "an ASCII character")
+                .at_line(ix + 1)
+                .into());
+        }
+    }
+
+    let mut input_iter = split_numbered_groups(input);
+
+    // An empty input has no groups at all
+    let missing = |expected| ParseError::in_block(input, &input[input.len()..], expected);
+
+    let (start, rule_lines) = input_iter.next().ok_or_else(|| missing("the rules"))?;
+    let rules = parse_lines(rule_lines, NumberedRule::from_str).map_err(|e| e.at_line(start))?;
+    // The rule numbers may have gaps, so we put each rule at its own index
+    // and fill the gaps with rules that match nothing
+    // We may .unwrap() here, because a group has at least one line
+    let rule_count = rules.iter().map(|nr| nr.0 + 1).max().unwrap();
+    let mut indexed_rules = vec![Rule::Multi(vec![]); rule_count];
+    for NumberedRule(number, rule) in rules {
+        indexed_rules[number] = rule;
+    }
+
+    // The matching uses the rule numbers as indices, so they must not go past the last rule
+    for (ix, line) in rule_lines.lines().enumerate() {
+        // We may .unwrap() here, because the line has parsed with its ':'
+        let alternatives = line.split(':').nth(1).unwrap();
+        for token in alternatives.split_ascii_whitespace() {
+            if token.parse::<usize>().is_ok_and(|n| n >= rule_count) {
+                let expected = format!("a rule number up to {}", rule_count - 1);
+                return Err(ParseError::new(line, token, expected)
+                    .at_line(start + ix)
+                    .into());
+            }
+        }
+    }
+
+    let (start, messages) = input_iter.next().ok_or_else(|| missing("the messages"))?;
+    let messages = messages
+        .lines()
+        .enumerate()
+        .map(|(ix, message)| (start + ix, message))
+        .collect::<Vec<_>>();
+
+    Ok((indexed_rules, messages))
+}
+
+/// Finds a rule that turns into itself through the alternatives of one rule each
+///
+/// Such alternatives don't make the stack longer, so `matches` would never stop expanding them.
+fn looping_rule(rules: &[Rule]) -> Option<usize> {
+    // We may use the index here, because the caller has checked the rule values
+    let single_rules = |n: usize| -> Vec<usize> {
+        match &rules[n] {
+            Rule::Multi(v) => v.iter().filter(|a| a.len() == 1).map(|a| a[0]).collect(),
+            Rule::Single(_) => vec![],
+        }
+    };
+
+    (0..rules.len()).find(|&start| {
+        let mut seen = HashSet::new();
+        let mut to_visit = single_rules(start);
+        while let Some(n) = to_visit.pop() {
+            if n == start {
+                return true;
+            }
+            if seen.insert(n) {
+                to_visit.extend(single_rules(n));
+            }
+        }
+        false
+    })
+}
+
+fn cloned_rev(v: &[usize]) -> Vec<StackedValue> {
+    v.iter().cloned().rev().map(StackedValue::Usize).collect()
+}
+
+/// Matches the message against the rules on the stack, trying the alternatives one by one
+///
+/// The ambiguous rules may take exponential time here, so we give up after `MAX_STEPS` calls.
+fn matches(
+    message: &str,
+    rules: &[Rule],
+    mut stack: Vec<StackedValue>,
+    steps: &mut usize,
+) -> Result<bool> {
+    *steps += 1;
+    if *steps > MAX_STEPS {
+        bail!(
+            "The rules are too ambiguous, it takes more than {} steps of trying the alternatives",
+            MAX_STEPS
+        );
+    }
+
+    if stack.len() > message.len() {
+        return Ok(false);
+    } else if stack.is_empty() || message.is_empty() {
+        return Ok(stack.is_empty() && message.is_empty());
+    }
+
+    // We may call .unwrap() here because we checked the stack length above
+    let c = stack.pop().unwrap();
+
+    match c {
+        StackedValue::Char(c) => {
+            // We may call .unwrap() here because we checked the message length above
+            if message.chars().next().unwrap() == c {
+                // We can use the index because we have verified that the input data is ASCII
+                return matches(&message[1..], rules, stack, steps);
+            }
+        }
+        StackedValue::Usize(n) => {
+            // get_data and the second part have checked the rule numbers to use the index here
+            let values = match &rules[n] {
+                Rule::Single(c) => {
+                    vec![vec![StackedValue::Char(*c)]]
+                }
+                Rule::Multi(v) => v.iter().map(|v| cloned_rev(v)).collect(),
+            };
+            for rule in values {
+                let mut ext_stack = stack.clone();
+                ext_stack.extend(rule);
+
+                if matches(message, rules, ext_stack, steps)? {
+                    return Ok(true);
+                }
+            }
+        }
+    }
+    Ok(false)
+}
+
+#[derive(Debug, Clone)]
+enum StackedValue {
+    Char(char),
+    Usize(usize),
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+enum Rule {
+    Single(char),
+    Multi(Vec<Vec<usize>>),
+}
+
+#[derive(Debug)]
+struct NumberedRule(usize, Rule);
+
+impl FromStr for NumberedRule {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut value_iter = value.split(':');
+        // We may .unwrap() here, because split always gives at least one item
+        let number = value_iter.next().unwrap();
+        let rest = value_iter
+            .next()
+            .ok_or_else(|| ParseError::after(value, number, "\":\""))?
+            .trim_start();
+        // The rules are stored at their numbers, so we keep the numbers small
+        let number = usize::from(parse_token::<u16>(value, number, "a rule number")?);
+        match rest.chars().next() {
+            Some('"') => Ok(NumberedRule(
+                number,
+                Rule::Single(
+                    rest.chars()
+                        .nth(1)
+                        .ok_or_else(|| ParseError::after(value, rest, "a character"))?,
+                ),
+            )),
+            _ => {
+                let vecs = rest
+                    .split(" | ")
+                    .map(|s| {
+                        let alternative = s
+                            .split_ascii_whitespace()
+                            .map(|n| parse_token(value, n, "a rule number"))
+                            .collect::<Result<Vec<_>, _>>()?;
+                        // An empty alternative would shrink the stack and let a rule loop forever
+                        match alternative.is_empty() {
+                            true => Err(ParseError::new(value, s.trim_end(), "a rule number")),
+                            false => Ok(alternative),
+                        }
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(NumberedRule(number, Rule::Multi(vecs)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("../input/input.txt");
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+    const EXAMPLE_2: &str = include_str!("../input/example2.txt");
+
+    #[test]
+    fn same_answers_for_lf_and_crlf() -> Result<()> {
+        let lf = INPUT.replace("\r\n", "\n");
+        let crlf = lf.replace('\n', "\r\n");
+
+        assert_eq!(part1(&lf)?, part1(&crlf)?);
+        assert_eq!(part2(&lf)?, part2(&crlf)?);
+        Ok(())
+    }
+
+    #[test]
+    fn part1_examples() -> Result<()> {
+        assert_eq!(part1(EXAMPLE)?, 2);
+        assert_eq!(part1(EXAMPLE_2)?, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(EXAMPLE_2)?, 12);
+        Ok(())
+    }
+
+    #[test]
+    fn points_at_the_bad_input() {
+        let error = |input: &str| {
+            let error = part1(input).err().unwrap();
+            let error = error.downcast_ref::<ParseError>().unwrap().clone();
+            (error.line, error.column, error.expected)
+        };
+        let expected = |line, column, expected: &str| (Some(line), column, expected.to_string());
+
+        assert_eq!(
+            error("0: 1 2\n1: \"a\"\n2: 3\n\nab\n"),
+            expected(3, 4, "a rule number up to 2")
+        );
+        assert_eq!(
+            error("0: 1\n1: \"a\"\n\na\nä\n"),
+            expected(5, 1, "an ASCII character")
+        );
+        assert_eq!(error("0: 1\n1: \"a\"\n"), expected(3, 1, "the messages"));
+    }
+
+    #[test]
+    fn names_the_missing_rules() {
+        let error = format!("{:#}", part1("1: \"a\"\n\na\n").err().unwrap());
+        assert!(error.contains("no rule 0"), "{}", error);
+        let error = format!("{:#}", part2("0: 1\n1: \"a\"\n\na\n").err().unwrap());
+        assert!(error.contains("the last rule is 1"), "{}", error);
+    }
+
+    #[test]
+    fn matches_every_alternative_of_rule_0() -> Result<()> {
+        assert_eq!(part1("0: \"a\"\n\na\nb\n")?, 1);
+        assert_eq!(part1("0: 1 | 2 2\n1: \"a\"\n2: \"b\"\n\na\nbb\nab\n")?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn names_the_limit_of_the_ambiguous_rules() {
+        // Rule 1 splits the a's into ones and twos in a Fibonacci number of ways, and none of them ends with a c
+        let input = format!(
+            "0: 1 3\n1: 2 | 4 | 2 1 | 4 1\n2: \"a\"\n3: \"c\"\n4: 2 2\n\nac\n{}b\n",
+            "a".repeat(60)
+        );
+        let error = format!("{:#}", part1(&input).err().unwrap());
+        assert!(
+            error.starts_with("Can't match the message on line 8"),
+            "{}",
+            error
+        );
+        assert!(
+            error.contains(&format!("more than {} steps", MAX_STEPS)),
+            "{}",
+            error
+        );
+    }
+}
@@ -0,0 +1,289 @@
+//! Jigsaws of square tiles with sea monsters in the picture
+//!
+//! The borders of the tiles are unique even when reversed, and none of them reads the same both ways, so each tile fits
+//! its neighbors in one place and one orientation only.
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::ops::RangeInclusive;
+
+/// The side of the real jigsaw in tiles
+pub const PUZZLE_SCALE: usize = 12;
+
+/// The sides of the jigsaws, as many as there are borders for
+pub const SCALES: RangeInclusive<usize> = 3..=15;
+
+const TILE_SIZE: usize = 10;
+const INNER_SIZE: usize = TILE_SIZE - 2;
+
+const MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+type Picture = Vec<Vec<bool>>;
+
+/// The number of the borders that don't read the same both ways, up to the reversal, by the class of their corners
+const CAPACITY: [usize; 3] = [120, 120, 256];
+
+/// Makes a jigsaw of `scale` by `scale` tiles, at least 3 and at most 15
+///
+/// The picture of 2 by 2 tiles would be 16 pixels wide, too narrow for a monster. There are only 496 borders that
+/// don't read the same both ways, up to the reversal, and the 15 by 15 jigsaw takes 480 of them. They are split by
+/// their corners though, see [`corners`].
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    let side = scale.clamp(*SCALES.start(), *SCALES.end());
+
+    let monster = MONSTER
+        .iter()
+        .map(|l| l.chars().map(|c| c == '#').collect::<Vec<_>>())
+        .collect::<Picture>();
+
+    let (picture, monsters) = loop {
+        let mut picture = (0..side * INNER_SIZE)
+            .map(|_| {
+                (0..side * INNER_SIZE)
+                    .map(|_| rng.gen_bool(0.2))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Picture>();
+        let planted = plant_monsters(&mut rng, &mut picture, &monster);
+
+        // Some noise may look like a monster too, and then we can't tell how many there are
+        let found = orientations(&picture)
+            .iter()
+            .map(|p| find_monsters(p, &monster))
+            .filter(|&f| f > 0)
+            .collect::<Vec<_>>();
+        if found == [planted] {
+            break (picture, planted);
+        }
+    };
+
+    // The corners of the tiles are shared by the borders around them
+    let corners = corners(&mut rng, side);
+    let mut pools = pools();
+    for pool in pools.iter_mut() {
+        pool.shuffle(&mut rng);
+    }
+    let mut border = |from: bool, to: bool| {
+        // We may .unwrap() here, because the corners fit into the pools
+        let border = pools[class(from, to)].pop().unwrap();
+        // Either way fits the equal corners, and the pool of the different ones starts with false
+        match border[0] == from && (from != to || rng.gen_bool(0.5)) {
+            true => border,
+            false => border.into_iter().rev().collect(),
+        }
+    };
+
+    // The horizontal borders go left to right, the vertical ones top to bottom
+    let horizontal = (0..=side)
+        .map(|y| {
+            (0..side)
+                .map(|x| border(corners[y][x], corners[y][x + 1]))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let vertical = (0..side)
+        .map(|y| {
+            (0..=side)
+                .map(|x| border(corners[y][x], corners[y + 1][x]))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut ids = (1000..10_000_u64).collect::<Vec<_>>();
+    ids.shuffle(&mut rng);
+    ids.truncate(side * side);
+
+    let mut tiles = vec![];
+    for y in 0..side {
+        for x in 0..side {
+            let mut tile = vec![vec![false; TILE_SIZE]; TILE_SIZE];
+            for i in 0..TILE_SIZE {
+                tile[0][i] = horizontal[y][x][i];
+                tile[TILE_SIZE - 1][i] = horizontal[y + 1][x][i];
+                tile[i][0] = vertical[y][x][i];
+                tile[i][TILE_SIZE - 1] = vertical[y][x + 1][i];
+            }
+            for (i, row) in tile[1..=INNER_SIZE].iter_mut().enumerate() {
+                row[1..=INNER_SIZE].copy_from_slice(
+                    &picture[y * INNER_SIZE + i][x * INNER_SIZE..(x + 1) * INNER_SIZE],
+                );
+            }
+
+            // We may .unwrap() here, because there are always 8 orientations
+            let tile = orientations(&tile).choose(&mut rng).unwrap().clone();
+            tiles.push((ids[y * side + x], tile));
+        }
+    }
+
+    let corner_product = [0, side - 1, side * (side - 1), side * side - 1]
+        .iter()
+        .map(|&ix| ids[ix])
+        .product::<u64>();
+    let roughness = picture.iter().flatten().filter(|&&p| p).count()
+        - monsters * monster.iter().flatten().filter(|&&m| m).count();
+
+    tiles.shuffle(&mut rng);
+
+    Generated {
+        input: tiles
+            .iter()
+            .map(|(id, tile)| format!("Tile {}:\n{}", id, to_text(tile)))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        answers: vec![
+            Some(Answer::from(corner_product)),
+            Some(Answer::from(roughness)),
+        ],
+        params: Params::default().with(&crate::WORLD_SIZE, side),
+    }
+}
+
+/// The class of the borders between the corners: `00`, `11`, and `01` or `10`, which are one another reversed
+fn class(from: bool, to: bool) -> usize {
+    match (from, to) {
+        (false, false) => 0,
+        (true, true) => 1,
+        _ => 2,
+    }
+}
+
+/// Every border that doesn't read the same both ways once, up to the reversal, by the class of its corners
+fn pools() -> [Vec<Vec<bool>>; 3] {
+    let mut pools = [vec![], vec![], vec![]];
+
+    for &(from, to) in &[(false, false), (true, true), (false, true)] {
+        for inner in 0..1_u32 << INNER_SIZE {
+            let mut border = vec![from];
+            border.extend((0..INNER_SIZE).map(|bit| inner >> bit & 1 == 1));
+            border.push(to);
+
+            // The smaller of the two readings stands for both
+            let reversed = border.iter().rev().copied().collect::<Vec<_>>();
+            if from != to || border < reversed {
+                pools[class(from, to)].push(border);
+            }
+        }
+    }
+
+    pools
+}
+
+/// The corners of the tiles, such that the borders between them fit into the [`CAPACITY`] of their classes
+///
+/// The random corners of the bigger jigsaws need more borders of one class than there are every so often, so after a
+/// few tries we fall back to stripes. Their horizontal borders split evenly between `00` and `11`, at most 8 rows
+/// of 15 each, and the 240 vertical ones are all `01` or `10`.
+fn corners(rng: &mut impl Rng, side: usize) -> Picture {
+    for _ in 0..100 {
+        let corners = (0..=side)
+            .map(|_| (0..=side).map(|_| rng.gen_bool(0.5)).collect::<Vec<_>>())
+            .collect::<Picture>();
+
+        let mut needed = [0; 3];
+        for y in 0..=side {
+            for x in 0..=side {
+                if x < side {
+                    needed[class(corners[y][x], corners[y][x + 1])] += 1;
+                }
+                if y < side {
+                    needed[class(corners[y][x], corners[y + 1][x])] += 1;
+                }
+            }
+        }
+        if needed.iter().zip(&CAPACITY).all(|(n, c)| n <= c) {
+            return corners;
+        }
+    }
+
+    let phase = rng.gen_bool(0.5);
+    (0..=side)
+        .map(|y| vec![(y % 2 == 0) != phase; side + 1])
+        .collect()
+}
+
+/// Puts monsters at random places where they don't overlap and returns their number
+fn plant_monsters(rng: &mut impl Rng, picture: &mut Picture, monster: &Picture) -> usize {
+    let (height, width) = (monster.len(), monster[0].len());
+    let mut taken = vec![vec![false; picture[0].len()]; picture.len()];
+    let mut planted = 0;
+
+    for _ in 0..picture.len() * picture[0].len() / 200 + 1 {
+        let y = rng.gen_range(0..=picture.len() - height);
+        let x = rng.gen_range(0..=picture[0].len() - width);
+
+        let free = (y..y + height).all(|y| (x..x + width).all(|x| !taken[y][x]));
+        if !free {
+            continue;
+        }
+
+        for dy in 0..height {
+            for dx in 0..width {
+                taken[y + dy][x + dx] = true;
+                picture[y + dy][x + dx] |= monster[dy][dx];
+            }
+        }
+        planted += 1;
+    }
+
+    planted
+}
+
+fn find_monsters(picture: &Picture, monster: &Picture) -> usize {
+    let (height, width) = (monster.len(), monster[0].len());
+    if picture.len() < height || picture[0].len() < width {
+        return 0;
+    }
+
+    (0..=picture.len() - height)
+        .flat_map(|y| (0..=picture[0].len() - width).map(move |x| (y, x)))
+        .filter(|&(y, x)| {
+            (0..height).all(|dy| (0..width).all(|dx| !monster[dy][dx] || picture[y + dy][x + dx]))
+        })
+        .count()
+}
+
+/// The four rotations of the square picture and of its mirror image
+fn orientations(picture: &Picture) -> Vec<Picture> {
+    let mirrored = picture
+        .iter()
+        .map(|r| r.iter().rev().copied().collect())
+        .collect::<Picture>();
+
+    let mut orientations = vec![];
+    for start in [picture.clone(), mirrored] {
+        let mut current = start;
+        for _ in 0..4 {
+            let rotated = (0..current.len())
+                .map(|y| {
+                    (0..current.len())
+                        .map(|x| current[current.len() - 1 - x][y])
+                        .collect()
+                })
+                .collect();
+            orientations.push(std::mem::replace(&mut current, rotated));
+        }
+    }
+
+    orientations
+}
+
+fn to_text(picture: &Picture) -> String {
+    picture
+        .iter()
+        .map(|r| {
+            let mut line = r
+                .iter()
+                .map(|&p| if p { '#' } else { '.' })
+                .collect::<String>();
+            line.push('\n');
+            line
+        })
+        .collect()
+}
@@ -0,0 +1,586 @@
+//! Grid solution
+//! because I still don't like Vec<Vec<_>> stuff :3
+
+pub mod generate;
+
+use anyhow::{anyhow, bail, Result};
+use aoc_common::grid::Grid;
+use aoc_common::parse::{parse_token, split_numbered_groups, OrInvalid};
+use aoc_common::{Param, Params, ParseError};
+use std::collections::{BTreeMap, VecDeque};
+use std::convert::TryFrom;
+use std::ops::RangeInclusive;
+use tracing::{debug, trace};
+
+// The tiles of the real input are 10 pixels wide
+const TILE_SIZE: Param = Param {
+    name: "tile-size",
+    default: "10",
+    help: "The side of a tile in pixels",
+};
+
+// The real input has 144 tiles for a square image
+const WORLD_SIZE: Param = Param {
+    name: "world-size",
+    default: "12",
+    help: "The side of the image in tiles",
+};
+
+pub const PARAMS: &[Param] = &[TILE_SIZE, WORLD_SIZE];
+
+pub fn part1(input: &str) -> Result<u64> {
+    part1_with(input, &Params::default())
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    part2_with(input, &Params::default())
+}
+
+pub fn part1_with(input: &str, params: &Params) -> Result<u64> {
+    let world = fill_world(input, params)?;
+    let product = world.corner_id_product().or_invalid()?;
+    Ok(product)
+}
+
+pub fn part2_with(input: &str, params: &Params) -> Result<usize> {
+    let world = fill_world(input, params)?;
+    let mut gworld = GluedWorld::try_from(world)?;
+
+    // |                  # |
+    // |#    ##    ##    ###|
+    // | #  #  #  #  #  #   |
+    // 3x20
+    let monster_str = "                  # \n#    ##    ##    ###\n #  #  #  #  #  #   ";
+    let monster_pixel = |c| match c {
+        '#' => Some(1),
+        ' ' => Some(0),
+        _ => None,
+    };
+    let monster = Grid::parse(monster_str, monster_pixel, "a pixel")?;
+
+    gworld.remove_pattern(&monster)?;
+    Ok(gworld.habitat())
+}
+
+pub fn parse(input: &str) -> Result<impl Sized> {
+    get_tiles(input, Params::default().get(&TILE_SIZE)?)
+}
+
+fn get_tiles(input: &str, tile_size: usize) -> Result<VecDeque<Tile>> {
+    // The tiles have borders all around
+    if tile_size < 2 {
+        bail!("The tiles must be at least 2 pixels wide");
+    }
+
+    let tiles = split_numbered_groups(input)
+        .map(|(start, group)| Tile::parse(group, tile_size).map_err(|e| e.at_line(start)))
+        .collect::<Result<_, _>>()?;
+    Ok(tiles)
+}
+
+fn fill_world(input: &str, params: &Params) -> Result<World> {
+    let mut tiles = get_tiles(input, params.get(&TILE_SIZE)?)?;
+    let world_size: usize = params.get(&WORLD_SIZE)?;
+
+    if world_size == 0 {
+        bail!("The image must be at least 1 tile wide");
+    }
+    let tile_count = world_size
+        .checked_mul(world_size)
+        .ok_or_else(|| anyhow!("An image of {0}x{0} tiles is too big", world_size))?;
+    if tiles.len() < tile_count {
+        bail!(
+            "An image of {0}x{0} tiles needs {1} tiles, but the input has {2}",
+            world_size,
+            tile_count,
+            tiles.len()
+        );
+    }
+
+    // Just place first_tile in the middle of the World
+    // We may use .unwrap() here because we checked the length
+    let mut world = World::new(tiles.pop_front().unwrap(), world_size);
+
+    // The number of the tiles that didn't fit since the world last grew
+    let mut misses = 0;
+
+    while let Some(current_tile) = tiles.pop_front() {
+        match world.try_place(current_tile) {
+            Ok(()) => misses = 0,
+            Err(tile) => {
+                trace!(id = tile.id, "doesn't fit yet");
+                tiles.push_back(tile);
+                misses += 1;
+            }
+        }
+
+        // Every tile left has been tried since then, so they will never fit
+        if misses > 0 && misses == tiles.len() {
+            bail!("The tiles don't fit together");
+        }
+    }
+
+    Ok(world)
+}
+
+#[derive(Debug)]
+struct Tile {
+    id: u64,
+    grid: Grid<u8>,
+    side: usize,
+}
+
+impl Tile {
+    fn upper(&self) -> impl Iterator<Item = &u8> {
+        self.grid.row(0).iter()
+    }
+
+    fn lower(&self) -> impl Iterator<Item = &u8> {
+        self.grid.row(self.side - 1).iter()
+    }
+
+    fn left(&self) -> impl Iterator<Item = &u8> {
+        self.grid.column(0)
+    }
+
+    fn right(&self) -> impl Iterator<Item = &u8> {
+        self.grid.column(self.side - 1)
+    }
+
+    // Counterclockwise
+    fn rotate(&mut self) {
+        self.grid = self.grid.rotated();
+    }
+
+    fn flip(&mut self) {
+        self.grid = self.grid.transposed();
+    }
+
+    fn inner(&self) -> Grid<u8> {
+        self.grid.sub_grid(1..(self.side - 1), 1..(self.side - 1))
+    }
+}
+
+impl Tile {
+    /// The line numbers of the errors are relative to the tile
+    fn parse(value: &str, side: usize) -> Result<Self, ParseError> {
+        let mut value_lines = value.lines();
+        let header = value_lines
+            .next()
+            .ok_or_else(|| ParseError::new(value, value, "a tile header").at_line(1))?;
+        let id = header.trim_start_matches("Tile ").trim_end_matches(':');
+        let id = parse_token(header, id, "a tile id").map_err(|e| e.at_line(1))?;
+
+        let mut grid = Grid::new(side, side, 0);
+
+        for (row_n, row) in value_lines.enumerate() {
+            // The tile starts with the header, so the rows start from the second line
+            let line_n = row_n + 2;
+            // We have to check the sizes before indexing the array
+            if row_n >= side {
+                return Err(ParseError::new(row, row, "the end of the tile").at_line(line_n));
+            }
+            for (col_n, (ix, char)) in row.char_indices().enumerate() {
+                if col_n >= side {
+                    let error = ParseError::new(row, &row[ix..], "the end of the row");
+                    return Err(error.at_line(line_n));
+                }
+                if char == '#' {
+                    grid[(row_n, col_n)] = 1;
+                }
+            }
+        }
+
+        Ok(Tile { id, grid, side })
+    }
+}
+
+#[derive(Debug)]
+struct World {
+    map: BTreeMap<(i32, i32), Tile>,
+    /// The side in tiles
+    size: usize,
+    limit_x: Option<RangeInclusive<i32>>,
+    limit_y: Option<RangeInclusive<i32>>,
+}
+
+enum PlaceDirection {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl PlaceDirection {
+    fn coord(&self, other: &(i32, i32)) -> (i32, i32) {
+        match self {
+            PlaceDirection::Left => (other.0 - 1, other.1),
+            PlaceDirection::Right => (other.0 + 1, other.1),
+            PlaceDirection::Top => (other.0, other.1 + 1),
+            PlaceDirection::Bottom => (other.0, other.1 - 1),
+        }
+    }
+
+    fn suit(&self, old_tile: &Tile, new_tile: &Tile) -> bool {
+        match self {
+            PlaceDirection::Left => old_tile.left().eq(new_tile.right()),
+            PlaceDirection::Right => old_tile.right().eq(new_tile.left()),
+            PlaceDirection::Top => old_tile.upper().eq(new_tile.lower()),
+            PlaceDirection::Bottom => old_tile.lower().eq(new_tile.upper()),
+        }
+    }
+}
+
+static PLACE_DIRECTIONS: &[PlaceDirection] = &[
+    PlaceDirection::Left,
+    PlaceDirection::Right,
+    PlaceDirection::Top,
+    PlaceDirection::Bottom,
+];
+
+enum PlaceAction {
+    Rotate,
+    Flip,
+}
+
+static PLACE_ACTIONS: &[PlaceAction] = &[
+    PlaceAction::Rotate,
+    PlaceAction::Rotate,
+    PlaceAction::Rotate,
+    PlaceAction::Flip,
+    PlaceAction::Rotate,
+    PlaceAction::Rotate,
+    PlaceAction::Rotate,
+];
+
+impl World {
+    fn new(first_tile: Tile, size: usize) -> Self {
+        let mut map = BTreeMap::new();
+        map.insert((0, 0), first_tile);
+
+        World {
+            map,
+            size,
+            limit_x: None,
+            limit_y: None,
+        }
+    }
+
+    fn try_place(&mut self, mut new_tile: Tile) -> ::std::result::Result<(), Tile> {
+        let mut new_coord_confirmed = None;
+        let mut place_action_iter = PLACE_ACTIONS.iter();
+
+        'outer: loop {
+            // For each tile in map
+            for (old_coord, old_tile) in self.map.iter() {
+                // Try to place in the:
+                // Left | Right | Up | Down
+                for direction in PLACE_DIRECTIONS {
+                    let new_coord = direction.coord(old_coord);
+
+                    if !self.within_limits(&new_coord) {
+                        continue;
+                    }
+
+                    if self.map.contains_key(&new_coord) {
+                        continue;
+                    }
+
+                    if direction.suit(old_tile, &new_tile) {
+                        new_coord_confirmed = Some(new_coord);
+                        break 'outer;
+                    }
+                }
+            }
+            // If not - choose 1 in order
+            // Rotate, Rotate, Rotate, Flip, Rotate, Rotate, Rotate
+            // If not - Err
+            match place_action_iter.next() {
+                Some(action) => match action {
+                    PlaceAction::Rotate => new_tile.rotate(),
+                    PlaceAction::Flip => new_tile.flip(),
+                },
+                None => break 'outer,
+            }
+        }
+
+        match new_coord_confirmed {
+            None => Err(new_tile),
+            Some(ncc) => {
+                debug!(id = new_tile.id, x = ncc.0, y = ncc.1, "placed");
+                self.map.insert(ncc, new_tile);
+                self.update_limits();
+                Ok(())
+            }
+        }
+    }
+
+    fn within_limits(&self, new_coord: &(i32, i32)) -> bool {
+        if let Some(lim_x) = &self.limit_x {
+            if !lim_x.contains(&new_coord.0) {
+                return false;
+            }
+        }
+        if let Some(lim_y) = &self.limit_y {
+            if !lim_y.contains(&new_coord.1) {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn update_limits(&mut self) {
+        if self.limit_x.is_none() {
+            // We may use .unwrap() here because we have at least one tile in the world
+            let min_x = self.map.keys().map(|x| x.0).min().unwrap();
+            let max_x = self.map.keys().map(|x| x.0).max().unwrap();
+
+            if max_x - min_x >= self.size as i32 - 1 {
+                self.limit_x = Some(RangeInclusive::new(min_x, max_x));
+            }
+        }
+        if self.limit_y.is_none() {
+            // We may use .unwrap() here because we have at least one tile in the world
+            let min_y = self.map.keys().map(|x| x.1).min().unwrap();
+            let max_y = self.map.keys().map(|x| x.1).max().unwrap();
+
+            if max_y - min_y >= self.size as i32 - 1 {
+                self.limit_y = Some(RangeInclusive::new(min_y, max_y));
+            }
+        }
+    }
+
+    fn limits_as_tuple(&self) -> Option<(i32, i32, i32, i32)> {
+        match (&self.limit_x, &self.limit_y) {
+            (Some(ref limit_x), Some(ref limit_y)) => {
+                let min_x = *limit_x.start();
+                let max_x = *limit_x.end();
+                let min_y = *limit_y.start();
+                let max_y = *limit_y.end();
+                Some((min_x, max_x, min_y, max_y))
+            }
+            _ => None,
+        }
+    }
+
+    fn corner_id_product(&self) -> Option<u64> {
+        let (min_x, max_x, min_y, max_y) = self.limits_as_tuple()?;
+
+        let coords = [
+            (min_x, min_y),
+            (min_x, max_y),
+            (max_x, min_y),
+            (max_x, max_y),
+        ];
+
+        // We may use .unwrap() here because we DEFINITELY have elements with these coords
+        Some(coords.iter().map(|c| self.map.get(c).unwrap().id).product())
+    }
+}
+
+struct GluedWorld {
+    grid: Grid<u8>,
+}
+
+impl TryFrom<World> for GluedWorld {
+    type Error = anyhow::Error;
+
+    fn try_from(world: World) -> Result<Self, Self::Error> {
+        let (min_x, max_x, min_y, max_y) = world.limits_as_tuple().or_invalid()?;
+
+        // Let's make sure that the world size is correct and that all the tiles are filled
+        let size = world.size;
+        if world.map.len() != size * size
+            || max_x - min_x + 1 != size as i32
+            || max_y - min_y + 1 != size as i32
+        {
+            bail!("The tiles don't fill an image of {0}x{0} tiles", size);
+        }
+
+        // The rows of the world go up, while the rows of the grid go down
+        // Then we may use .unwrap() here safely
+        let inners = (min_y..=max_y)
+            .rev()
+            .map(|y| {
+                (min_x..=max_x)
+                    .map(|x| world.map.get(&(x, y)).unwrap().inner())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        // We may use .unwrap() here because the size is correct, so there are tiles
+        let inner_side = world.map.values().next().unwrap().side - 2;
+        let side = inner_side * size;
+
+        Ok(GluedWorld {
+            grid: Grid::from_fn(side, side, |(row, col)| {
+                inners[row / inner_side][col / inner_side][(row % inner_side, col % inner_side)]
+            }),
+        })
+    }
+}
+
+impl GluedWorld {
+    fn habitat(&self) -> usize {
+        self.grid.iter().filter(|&&v| v == 1).count()
+    }
+
+    fn remove_pattern(&mut self, pattern: &Grid<u8>) -> Result<()> {
+        let mut changed = false;
+        let mut place_action_iter = PLACE_ACTIONS.iter().chain(PLACE_ACTIONS.iter());
+
+        // The pixels of the pattern, relative to its top left corner
+        let pattern_pixels = pattern
+            .positions()
+            .filter(|&p| pattern[p] == 1)
+            .collect::<Vec<_>>();
+
+        loop {
+            let rows = (self.grid.height() + 1).saturating_sub(pattern.height());
+            let cols = (self.grid.width() + 1).saturating_sub(pattern.width());
+
+            for row in 0..rows {
+                for col in 0..cols {
+                    let found = pattern_pixels
+                        .iter()
+                        .all(|&(r, c)| self.grid[(row + r, col + c)] == 1);
+
+                    if !found {
+                        continue;
+                    }
+
+                    debug!(row, col, "found the pattern");
+                    for &(r, c) in pattern_pixels.iter() {
+                        self.grid[(row + r, col + c)] = 0;
+                    }
+                    changed = true;
+                }
+            }
+
+            if changed {
+                break;
+            }
+
+            match place_action_iter.next() {
+                Some(action) => match action {
+                    PlaceAction::Rotate => self.grid = self.grid.rotated(),
+                    PlaceAction::Flip => self.grid = self.grid.transposed(),
+                },
+                // Bad case: the pattern is not detected on the map completely
+                None => bail!("The pattern isn't in the image, however it's turned or flipped"),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("../input/input.txt");
+    // The worked example, a 3x3 jigsaw
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+    // One tile of the worked example
+    const TILE: &str = include_str!("../input/example_tile.txt");
+
+    #[test]
+    fn same_answers_for_lf_and_crlf() -> Result<()> {
+        let lf = INPUT.replace("\r\n", "\n");
+        let crlf = lf.replace('\n', "\r\n");
+
+        assert_eq!(part1(&lf)?, part1(&crlf)?);
+        assert_eq!(part2(&lf)?, part2(&crlf)?);
+        Ok(())
+    }
+
+    #[test]
+    fn solves_the_example() -> Result<()> {
+        let params = Params::default().with(&WORLD_SIZE, 3);
+        assert_eq!(part1_with(EXAMPLE, &params)?, 20899048083289);
+        assert_eq!(part2_with(EXAMPLE, &params)?, 273);
+        Ok(())
+    }
+
+    #[test]
+    fn parses_a_tile() -> Result<()> {
+        let tile = Tile::parse(TILE, 10)?;
+        assert_eq!(tile.id, 2311);
+        assert_eq!(
+            tile.upper().copied().collect::<Vec<_>>(),
+            [0, 0, 1, 1, 0, 1, 0, 0, 1, 0]
+        );
+        assert_eq!(
+            tile.lower().copied().collect::<Vec<_>>(),
+            [0, 0, 1, 1, 1, 0, 0, 1, 1, 1]
+        );
+        assert_eq!(
+            tile.left().copied().collect::<Vec<_>>(),
+            [0, 1, 1, 1, 1, 1, 0, 0, 1, 0]
+        );
+        assert_eq!(
+            tile.right().copied().collect::<Vec<_>>(),
+            [0, 0, 0, 1, 0, 1, 1, 0, 0, 1]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rotates_and_flips_a_tile() -> Result<()> {
+        let mut tile = Tile::parse(TILE, 10)?;
+        let right = tile.right().copied().collect::<Vec<_>>();
+        let left = tile.left().copied().collect::<Vec<_>>();
+
+        // Counterclockwise, so the right side goes up
+        tile.rotate();
+        assert_eq!(tile.upper().copied().collect::<Vec<_>>(), right);
+
+        tile.rotate();
+        tile.rotate();
+        tile.rotate();
+        tile.flip();
+        assert_eq!(tile.upper().copied().collect::<Vec<_>>(), left);
+        Ok(())
+    }
+
+    #[test]
+    fn reports_the_line_of_a_bad_tile_row() {
+        let tile = TILE.replacen("..##.#..#.", "..##.#..#..", 1);
+        let error = Tile::parse(&tile, 10).err().unwrap();
+        assert_eq!(error.line, Some(2));
+        assert_eq!(error.column, 11);
+    }
+
+    #[test]
+    fn solves_a_smaller_jigsaw() -> Result<()> {
+        let generated = generate::generate(0, 3);
+        let params = Params::default().with(&WORLD_SIZE, 3);
+        let answers = [
+            part1_with(&generated.input, &params)?.to_string(),
+            part2_with(&generated.input, &params)?.to_string(),
+        ];
+
+        for (answer, expected) in answers.iter().zip(&generated.answers) {
+            if let Some(expected) = expected {
+                assert_eq!(answer, &expected.to_string());
+            }
+        }
+        let error = part1(&generated.input).err().unwrap().to_string();
+        assert_eq!(
+            error,
+            "An image of 12x12 tiles needs 144 tiles, but the input has 9"
+        );
+        let params = Params::default().with(&WORLD_SIZE, usize::MAX);
+        assert!(part1_with(&generated.input, &params).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn generates_the_biggest_jigsaws() {
+        // Most random corners need more borders of one class than there are at this scale
+        for seed in 0..4 {
+            let input = generate::generate(seed, 15).input;
+            assert_eq!(input.matches("Tile").count(), 225);
+        }
+    }
+}
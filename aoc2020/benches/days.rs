@@ -0,0 +1,33 @@
+//! Times the parsing and each part of every day on the bundled inputs
+//!
+//! The parts parse the input themselves, so their times include the parsing.
+
+use aoc2020::days::DAYS;
+use aoc_common::{input, Params};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn days(c: &mut Criterion) {
+    for day in DAYS {
+        // We may .unwrap() here, because there is nothing to measure without the bundled input
+        let input = input::read_file(&day.default_input_path()).unwrap();
+        let mut group = c.benchmark_group(format!("day{:02}", day.number));
+
+        group.bench_function("parse", |b| b.iter(|| (day.parse)(black_box(&input))));
+
+        for (ix, part) in day.parts.iter().enumerate() {
+            group.bench_function(format!("part{}", ix + 1), |b| {
+                b.iter(|| part(black_box(&input), &Params::default()))
+            });
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group! {
+    name = benches;
+    // Some parts take seconds, so we take the minimum number of samples
+    config = Criterion::default().sample_size(10);
+    targets = days
+}
+criterion_main!(benches);
@@ -0,0 +1,87 @@
+//! Hashes the sources of every day, so that the cached answers of a day expire when its solution changes
+//!
+//! A day depends on its own crate and on `aoc-common`, so both go into its hash. So do the versions of the
+//! dependencies in `Cargo.lock` and the build profile, because an update of a dependency or an overflow check of
+//! the dev profile may change an answer too.
+
+use std::collections::hash_map::DefaultHasher;
+use std::env;
+use std::fs;
+use std::hash::Hasher;
+use std::io;
+use std::path::{Path, PathBuf};
+
+fn main() -> io::Result<()> {
+    // We may .unwrap() the variables here, because Cargo always sets them for the build scripts
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("..");
+    let common = root.join("aoc-common");
+    watch(&common);
+
+    let lock = root.join("Cargo.lock");
+    println!("cargo:rerun-if-changed={}", lock.display());
+    // The workspace of the fuzz targets has its own lock, and then there's none here
+    let lock = fs::read(lock).unwrap_or_default();
+    let profile = [env::var("PROFILE").unwrap(), env::var("OPT_LEVEL").unwrap()];
+
+    let mut hashes = vec![];
+    for day in 1..=25 {
+        let dir = root.join(format!("aoc{:02}", day));
+        watch(&dir);
+
+        // DefaultHasher may change with Rust, which only makes the old answers miss
+        let mut hasher = DefaultHasher::new();
+        hasher.write(&lock);
+        for setting in profile.iter() {
+            hasher.write(setting.as_bytes());
+        }
+        for crate_dir in [&common, &dir].iter() {
+            for path in files(crate_dir)? {
+                // We may .unwrap() here, because the files are found inside of the crate directory
+                hasher.write(
+                    path.strip_prefix(crate_dir)
+                        .unwrap()
+                        .to_string_lossy()
+                        .as_bytes(),
+                );
+                hasher.write(&fs::read(&path)?);
+            }
+        }
+        hashes.push(format!("0x{:016x}", hasher.finish()));
+    }
+
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("source_hashes.rs");
+    fs::write(
+        out,
+        format!(
+            "/// The hashes of the sources of the days, in the order of the days\nconst SOURCE_HASHES: [u64; 25] = [{}];\n",
+            hashes.join(", ")
+        ),
+    )
+}
+
+fn watch(dir: &Path) {
+    println!("cargo:rerun-if-changed={}", dir.join("src").display());
+    println!(
+        "cargo:rerun-if-changed={}",
+        dir.join("Cargo.toml").display()
+    );
+}
+
+/// The manifest and the sources of the crate, in a fixed order
+fn files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = vec![dir.join("Cargo.toml")];
+    let mut dirs = vec![dir.join("src")];
+
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            match path.is_dir() {
+                true => dirs.push(path),
+                false => files.push(path),
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
@@ -0,0 +1,240 @@
+//! The answers of the earlier runs, so that the slow days don't solve the same input again
+//!
+//! Every answer is a file named after the day, the part, the hash of the day's sources with the locked dependencies
+//! and the build profile, the options and the hash of the input bytes. The same input hits the cache whether it came from a file or from stdin, and a changed
+//! solution misses it until it has solved the input once more.
+
+use crate::days::Day;
+use crate::run::Input;
+use anyhow::{bail, Context, Result};
+use aoc_common::{Answer, Params};
+use std::collections::hash_map::DefaultHasher;
+use std::fs::{self, File};
+use std::hash::{Hash, Hasher};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+include!(concat!(env!("OUT_DIR"), "/source_hashes.rs"));
+
+/// The directory with one subdirectory of answers per day
+pub struct Cache {
+    dir: PathBuf,
+}
+
+/// Everything an answer depends on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    day: u8,
+    part: usize,
+    version: u64,
+    params: u64,
+    input: u64,
+}
+
+impl Key {
+    pub fn new(day: &Day, part: usize, params: &Params, input: u64) -> Self {
+        let mut hasher = DefaultHasher::new();
+        params.hash(&mut hasher);
+
+        Key {
+            day: day.number,
+            part,
+            version: version(day.number),
+            params: hasher.finish(),
+            input,
+        }
+    }
+
+    fn file_name(&self) -> String {
+        format!(
+            "part{}-{:016x}-{:016x}-{:016x}",
+            self.part, self.version, self.params, self.input
+        )
+    }
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Cache { dir: dir.into() }
+    }
+
+    /// `target/aoc-cache` of the workspace
+    pub fn default_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("target")
+            .join("aoc-cache")
+    }
+
+    fn day_dir(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{:02}", day))
+    }
+
+    /// The cached answer, if any
+    ///
+    /// An unreadable answer is just a miss, the part solves the input again and overwrites it.
+    pub fn get(&self, key: &Key) -> Option<Answer> {
+        let path = self.day_dir(key.day).join(key.file_name());
+        fs::read_to_string(path).ok().map(Answer::from)
+    }
+
+    pub fn put(&self, key: &Key, answer: &Answer) -> Result<()> {
+        let dir = self.day_dir(key.day);
+        fs::create_dir_all(&dir).with_context(|| format!("Can't create {}", dir.display()))?;
+
+        // The other runs never see a half-written answer, because the rename replaces the file at once
+        let path = dir.join(key.file_name());
+        let temp = dir.join(format!(".{}.{}", key.file_name(), std::process::id()));
+        fs::write(&temp, answer.to_string())
+            .and_then(|()| fs::rename(&temp, &path))
+            .with_context(|| format!("Can't write {}", path.display()))
+    }
+
+    /// Removes the answers of one day or of every day and returns their number
+    pub fn clear(&self, day: Option<u8>) -> Result<usize> {
+        let days = match day {
+            Some(day) => vec![day],
+            None => (1..=25).collect(),
+        };
+        self.remove(&days, |_, _| true)
+    }
+
+    /// Removes the answers of the older solutions and returns their number
+    pub fn prune(&self) -> Result<usize> {
+        let days = (1..=25).collect::<Vec<_>>();
+        self.remove(&days, |day, name| {
+            !name.contains(&format!("-{:016x}-", version(day)))
+        })
+    }
+
+    fn remove(&self, days: &[u8], stale: impl Fn(u8, &str) -> bool) -> Result<usize> {
+        let mut removed = 0;
+
+        for &day in days {
+            let dir = self.day_dir(day);
+            let entries = match fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e).with_context(|| format!("Can't read {}", dir.display())),
+            };
+
+            for entry in entries {
+                let path = entry?.path();
+                if stale(day, &path.file_name().unwrap_or_default().to_string_lossy()) {
+                    fs::remove_file(&path)
+                        .with_context(|| format!("Can't remove {}", path.display()))?;
+                    removed += 1;
+                }
+            }
+        }
+
+        Ok(removed)
+    }
+}
+
+/// The hash of the day's sources
+fn version(day: u8) -> u64 {
+    SOURCE_HASHES[usize::from(day) - 1]
+}
+
+/// The hash of the input bytes, the same for the text and for the file with it
+pub fn input_hash(input: &Input) -> Result<u64> {
+    let mut hasher = DefaultHasher::new();
+
+    match input {
+        Input::Text(text) => hasher.write(text.as_bytes()),
+        Input::File(path) => hash_file(path, &mut hasher)?,
+        // Hashing stdin would read it before the part, so its answers aren't cached
+        Input::Stdin => bail!("Can't hash stdin without reading it"),
+    }
+
+    Ok(hasher.finish())
+}
+
+/// Hashes the file in chunks, because the streamed inputs may not fit into memory
+fn hash_file(path: &Path, hasher: &mut DefaultHasher) -> Result<()> {
+    let mut file = File::open(path).with_context(|| format!("Can't read {}", path.display()))?;
+    let mut buf = vec![0; 1 << 16];
+
+    loop {
+        match file.read(&mut buf) {
+            Ok(0) => return Ok(()),
+            Ok(read) => hasher.write(&buf[..read]),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e).with_context(|| format!("Can't read {}", path.display())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    fn temp_cache(name: &str) -> Cache {
+        let dir =
+            std::env::temp_dir().join(format!("aoc2020-cache-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        Cache::new(dir)
+    }
+
+    #[test]
+    fn keeps_the_answers() -> Result<()> {
+        let cache = temp_cache("keeps");
+        let day = days::find(9).unwrap();
+        let key = Key::new(day, 1, &Params::default(), 42);
+
+        assert_eq!(cache.get(&key), None);
+        cache.put(&key, &Answer::from(127_u64))?;
+        assert_eq!(
+            cache.get(&key).map(|a| a.to_string()),
+            Some("127".to_string())
+        );
+
+        // Another input, part or option misses
+        assert_eq!(cache.get(&Key::new(day, 1, &Params::default(), 43)), None);
+        assert_eq!(cache.get(&Key::new(day, 2, &Params::default(), 42)), None);
+        let params = Params::default().with(&day.params[0], 5);
+        assert_eq!(cache.get(&Key::new(day, 1, &params, 42)), None);
+
+        assert_eq!(cache.clear(Some(8))?, 0);
+        assert_eq!(cache.clear(Some(9))?, 1);
+        assert_eq!(cache.get(&key), None);
+        Ok(())
+    }
+
+    #[test]
+    fn prunes_the_older_solutions() -> Result<()> {
+        let cache = temp_cache("prunes");
+        let day = days::find(1).unwrap();
+        let key = Key::new(day, 1, &Params::default(), 42);
+        let old = Key {
+            version: !key.version,
+            ..key
+        };
+
+        cache.put(&key, &Answer::from(1_u64))?;
+        cache.put(&old, &Answer::from(2_u64))?;
+        assert_eq!(cache.prune()?, 1);
+        assert!(cache.get(&key).is_some());
+        assert_eq!(cache.clear(None)?, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn the_same_input_has_the_same_hash() -> Result<()> {
+        let day = days::find(1).unwrap();
+        let path = day.default_input_path();
+        let text = aoc_common::input::read_file(&path)?;
+
+        assert_eq!(
+            input_hash(&Input::Text(text.clone()))?,
+            input_hash(&Input::File(path))?
+        );
+        assert_ne!(
+            input_hash(&Input::Text(text))?,
+            input_hash(&Input::Text(String::new()))?
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,409 @@
+use anyhow::{bail, Result};
+use aoc_common::generate::Generate;
+use aoc_common::stream::StreamPart;
+use aoc_common::{input, parser, parts, stream_parts, Param, Params, Parse, Part};
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+
+pub struct Day {
+    pub number: u8,
+    pub parse: Parse,
+    pub parts: &'static [Part],
+    /// The options of the day, e.g. `--preamble` of day 9
+    pub params: &'static [Param],
+    /// The same parts reading the input line by line, empty if the day needs the whole input at once
+    pub stream: &'static [StreamPart],
+    /// Makes the synthetic inputs with their known answers
+    pub generate: Generate,
+    /// The scale of the generated inputs that matches the real puzzle
+    pub puzzle_scale: usize,
+    /// The scales the generator can make
+    pub scales: RangeInclusive<usize>,
+}
+
+impl Day {
+    /// The directory of the day's crate, e.g. `aoc17`
+    pub fn dir(&self) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join(format!("aoc{:02}", self.number))
+    }
+
+    /// The bundled puzzle input
+    pub fn default_input_path(&self) -> PathBuf {
+        self.dir().join("input").join("input.txt")
+    }
+
+    /// The known-correct answers for the bundled input, one line per part
+    pub fn answers_path(&self) -> PathBuf {
+        self.dir().join("input").join("answers.txt")
+    }
+
+    pub fn expected_answers(&self) -> Result<Vec<String>> {
+        let answers = input::read_file(&self.answers_path())?;
+        Ok(answers.lines().map(|l| l.trim_end().to_string()).collect())
+    }
+
+    /// Checks that the generator makes the `scale` as it is, instead of clamping it
+    pub fn check_scale(&self, scale: usize) -> Result<()> {
+        if !self.scales.contains(&scale) {
+            match *self.scales.end() {
+                usize::MAX => bail!(
+                    "Day {} generates the scales from {} up",
+                    self.number,
+                    self.scales.start()
+                ),
+                end => bail!(
+                    "Day {} generates the scales from {} to {}",
+                    self.number,
+                    self.scales.start(),
+                    end
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses the arbitrary bytes and solves every part, dropping the answers and the errors
+    ///
+    /// The fuzz targets look for the panics and the hangs here, the errors are fine.
+    pub fn fuzz(&self, data: &[u8]) {
+        // The runner rejects the invalid UTF-8 before the days see it
+        if let Ok(input) = std::str::from_utf8(data) {
+            let _ = (self.parse)(input);
+            for part in self.parts {
+                let _ = part(input, &Params::default());
+            }
+        }
+    }
+}
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
+
+pub static DAYS: &[Day] = &[
+    Day {
+        number: 1,
+        parse: parser!(aoc01::parse),
+        parts: parts![with aoc01::part1_with, aoc01::part2_with],
+        params: aoc01::PARAMS,
+        stream: stream_parts![with aoc01::stream::part1_with, aoc01::stream::part2_with],
+        generate: aoc01::generate::generate,
+        puzzle_scale: aoc01::generate::PUZZLE_SCALE,
+        scales: aoc01::generate::SCALES,
+    },
+    Day {
+        number: 2,
+        parse: parser!(aoc02::parse),
+        parts: parts![with aoc02::part1_with, aoc02::part2_with],
+        params: aoc02::PARAMS,
+        stream: stream_parts![with aoc02::stream::part1_with, aoc02::stream::part2_with],
+        generate: aoc02::generate::generate,
+        puzzle_scale: aoc02::generate::PUZZLE_SCALE,
+        scales: aoc02::generate::SCALES,
+    },
+    Day {
+        number: 3,
+        parse: parser!(aoc03::parse),
+        parts: parts![aoc03::part1, aoc03::part2],
+        params: &[],
+        stream: &[],
+        generate: aoc03::generate::generate,
+        puzzle_scale: aoc03::generate::PUZZLE_SCALE,
+        scales: aoc03::generate::SCALES,
+    },
+    Day {
+        number: 4,
+        parse: parser!(aoc04::parse),
+        parts: parts![aoc04::part1, aoc04::part2],
+        params: &[],
+        stream: &[],
+        generate: aoc04::generate::generate,
+        puzzle_scale: aoc04::generate::PUZZLE_SCALE,
+        scales: aoc04::generate::SCALES,
+    },
+    Day {
+        number: 5,
+        parse: parser!(aoc05::parse),
+        parts: parts![aoc05::part1, aoc05::part2],
+        params: &[],
+        stream: stream_parts![aoc05::stream::part1, aoc05::stream::part2],
+        generate: aoc05::generate::generate,
+        puzzle_scale: aoc05::generate::PUZZLE_SCALE,
+        scales: aoc05::generate::SCALES,
+    },
+    Day {
+        number: 6,
+        parse: parser!(aoc06::parse),
+        parts: parts![aoc06::part1, aoc06::part2],
+        params: &[],
+        stream: &[],
+        generate: aoc06::generate::generate,
+        puzzle_scale: aoc06::generate::PUZZLE_SCALE,
+        scales: aoc06::generate::SCALES,
+    },
+    Day {
+        number: 7,
+        parse: parser!(aoc07::parse),
+        parts: parts![with aoc07::part1_with, aoc07::part2_with],
+        params: aoc07::PARAMS,
+        stream: &[],
+        generate: aoc07::generate::generate,
+        puzzle_scale: aoc07::generate::PUZZLE_SCALE,
+        scales: aoc07::generate::SCALES,
+    },
+    Day {
+        number: 8,
+        parse: parser!(aoc08::parse),
+        parts: parts![aoc08::part1, aoc08::part2],
+        params: &[],
+        stream: stream_parts![aoc08::stream::part1, aoc08::stream::part2],
+        generate: aoc08::generate::generate,
+        puzzle_scale: aoc08::generate::PUZZLE_SCALE,
+        scales: aoc08::generate::SCALES,
+    },
+    Day {
+        number: 9,
+        parse: parser!(aoc09::parse),
+        parts: parts![with aoc09::part1_with, aoc09::part2_with],
+        params: aoc09::PARAMS,
+        stream: stream_parts![with aoc09::stream::part1_with, aoc09::stream::part2_with],
+        generate: aoc09::generate::generate,
+        puzzle_scale: aoc09::generate::PUZZLE_SCALE,
+        scales: aoc09::generate::SCALES,
+    },
+    Day {
+        number: 10,
+        parse: parser!(aoc10::parse),
+        parts: parts![aoc10::part1, aoc10::part2],
+        params: &[],
+        stream: &[],
+        generate: aoc10::generate::generate,
+        puzzle_scale: aoc10::generate::PUZZLE_SCALE,
+        scales: aoc10::generate::SCALES,
+    },
+    Day {
+        number: 11,
+        parse: parser!(aoc11::parse),
+        parts: parts![aoc11::part1, aoc11::part2],
+        params: &[],
+        stream: &[],
+        generate: aoc11::generate::generate,
+        puzzle_scale: aoc11::generate::PUZZLE_SCALE,
+        scales: aoc11::generate::SCALES,
+    },
+    Day {
+        number: 12,
+        parse: parser!(aoc12::parse),
+        parts: parts![aoc12::part1, aoc12::part2],
+        params: &[],
+        stream: stream_parts![aoc12::stream::part1, aoc12::stream::part2],
+        generate: aoc12::generate::generate,
+        puzzle_scale: aoc12::generate::PUZZLE_SCALE,
+        scales: aoc12::generate::SCALES,
+    },
+    Day {
+        number: 13,
+        parse: parser!(aoc13::parse),
+        parts: parts![aoc13::part1, aoc13::part2],
+        params: &[],
+        stream: &[],
+        generate: aoc13::generate::generate,
+        puzzle_scale: aoc13::generate::PUZZLE_SCALE,
+        scales: aoc13::generate::SCALES,
+    },
+    Day {
+        number: 14,
+        parse: parser!(aoc14::parse),
+        parts: parts![aoc14::part1, aoc14::part2],
+        params: &[],
+        stream: stream_parts![aoc14::stream::part1, aoc14::stream::part2],
+        generate: aoc14::generate::generate,
+        puzzle_scale: aoc14::generate::PUZZLE_SCALE,
+        scales: aoc14::generate::SCALES,
+    },
+    Day {
+        number: 15,
+        parse: parser!(aoc15::parse),
+        parts: parts![with aoc15::part1_with, aoc15::part2_with],
+        params: aoc15::PARAMS,
+        stream: &[],
+        generate: aoc15::generate::generate,
+        puzzle_scale: aoc15::generate::PUZZLE_SCALE,
+        scales: aoc15::generate::SCALES,
+    },
+    Day {
+        number: 16,
+        parse: parser!(aoc16::parse),
+        parts: parts![aoc16::part1, aoc16::part2],
+        params: &[],
+        stream: &[],
+        generate: aoc16::generate::generate,
+        puzzle_scale: aoc16::generate::PUZZLE_SCALE,
+        scales: aoc16::generate::SCALES,
+    },
+    Day {
+        number: 17,
+        parse: parser!(aoc17::parse),
+        parts: parts![with aoc17::part1_with, aoc17::part2_with],
+        params: aoc17::PARAMS,
+        stream: &[],
+        generate: aoc17::generate::generate,
+        puzzle_scale: aoc17::generate::PUZZLE_SCALE,
+        scales: aoc17::generate::SCALES,
+    },
+    Day {
+        number: 18,
+        parse: parser!(aoc18::parse),
+        parts: parts![aoc18::part1, aoc18::part2],
+        params: &[],
+        stream: stream_parts![aoc18::stream::part1, aoc18::stream::part2],
+        generate: aoc18::generate::generate,
+        puzzle_scale: aoc18::generate::PUZZLE_SCALE,
+        scales: aoc18::generate::SCALES,
+    },
+    Day {
+        number: 19,
+        parse: parser!(aoc19::parse),
+        parts: parts![aoc19::part1, aoc19::part2],
+        params: &[],
+        stream: &[],
+        generate: aoc19::generate::generate,
+        puzzle_scale: aoc19::generate::PUZZLE_SCALE,
+        scales: aoc19::generate::SCALES,
+    },
+    Day {
+        number: 20,
+        parse: parser!(aoc20::parse),
+        parts: parts![with aoc20::part1_with, aoc20::part2_with],
+        params: aoc20::PARAMS,
+        stream: &[],
+        generate: aoc20::generate::generate,
+        puzzle_scale: aoc20::generate::PUZZLE_SCALE,
+        scales: aoc20::generate::SCALES,
+    },
+    Day {
+        number: 21,
+        parse: parser!(aoc21::parse),
+        parts: parts![aoc21::part1, aoc21::part2],
+        params: &[],
+        stream: &[],
+        generate: aoc21::generate::generate,
+        puzzle_scale: aoc21::generate::PUZZLE_SCALE,
+        scales: aoc21::generate::SCALES,
+    },
+    Day {
+        number: 22,
+        parse: parser!(aoc22::parse),
+        parts: parts![aoc22::part1, aoc22::part2],
+        params: &[],
+        stream: &[],
+        generate: aoc22::generate::generate,
+        puzzle_scale: aoc22::generate::PUZZLE_SCALE,
+        scales: aoc22::generate::SCALES,
+    },
+    Day {
+        number: 23,
+        parse: parser!(aoc23::parse),
+        parts: parts![with aoc23::part1_with, aoc23::part2_with],
+        params: aoc23::PARAMS,
+        stream: &[],
+        generate: aoc23::generate::generate,
+        puzzle_scale: aoc23::generate::PUZZLE_SCALE,
+        scales: aoc23::generate::SCALES,
+    },
+    Day {
+        number: 24,
+        parse: parser!(aoc24::parse),
+        parts: parts![with aoc24::part1_with, aoc24::part2_with],
+        params: aoc24::PARAMS,
+        stream: stream_parts![with aoc24::stream::part1_with, aoc24::stream::part2_with],
+        generate: aoc24::generate::generate,
+        puzzle_scale: aoc24::generate::PUZZLE_SCALE,
+        scales: aoc24::generate::SCALES,
+    },
+    Day {
+        number: 25,
+        parse: parser!(aoc25::parse),
+        parts: parts![with aoc25::part1_with],
+        params: aoc25::PARAMS,
+        stream: &[],
+        generate: aoc25::generate::generate,
+        puzzle_scale: aoc25::generate::PUZZLE_SCALE,
+        scales: aoc25::generate::SCALES,
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    /// The inputs that used to crash or hang the fuzz targets
+    #[test]
+    fn fuzz_regressions_pass() -> Result<()> {
+        let regressions = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("fuzz")
+            .join("regressions");
+
+        for day in DAYS {
+            let dir = regressions.join(format!("aoc{:02}", day.number));
+            if !dir.exists() {
+                continue;
+            }
+            for entry in std::fs::read_dir(dir)? {
+                day.fuzz(&std::fs::read(entry?.path())?);
+            }
+        }
+        Ok(())
+    }
+
+    /// The same check as `aoc2020 stress` with a few seeds, so a broken generator or solution fails `cargo test`
+    ///
+    /// The inputs are at most of the scale 20, a lot smaller than the real ones, so the tests stay quick without
+    /// optimizations. `aoc2020 stress` checks the real scale.
+    #[test]
+    fn generated_inputs_pass() -> Result<()> {
+        const SCALE: usize = 20;
+
+        for day in DAYS {
+            for seed in 0..3 {
+                let scale = SCALE.clamp(*day.scales.start(), day.puzzle_scale);
+                let mut generated = (day.generate)(seed, scale);
+                // The 30 million turns of day 15 don't depend on the scale, and they take minutes here
+                if day.number == 15 {
+                    generated.answers.truncate(1);
+                }
+                generated
+                    .verify(day.parts)
+                    .with_context(|| format!("Day {} seed {}", day.number, seed))?;
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_the_scales_the_generator_would_clamp() -> Result<()> {
+        // We may .unwrap() here, because the day exists
+        let day = find(20).unwrap();
+        day.check_scale(15)?;
+        let error = day.check_scale(16).err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Day 20 generates the scales from 3 to 15"
+        );
+        let error = find(17).unwrap().check_scale(0).err().unwrap();
+        assert_eq!(error.to_string(), "Day 17 generates the scales from 1 up");
+        Ok(())
+    }
+
+    #[test]
+    fn every_part_has_an_expected_answer() -> Result<()> {
+        for day in DAYS {
+            assert_eq!(day.expected_answers()?.len(), day.parts.len());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+//! The machine-readable records of the runs for `--format json`
+
+use crate::run::DayRun;
+use aoc_common::{ParseError, ParseErrorKind};
+use serde::Serialize;
+
+/// One part of one day
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: usize,
+    /// Always a string, so that the big numbers survive the JSON parsers with f64 numbers
+    pub answer: Option<String>,
+    /// `None` if the part didn't run at all, e.g. because the input can't be read
+    pub elapsed_ms: Option<f64>,
+    pub error: Option<ErrorRecord>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ErrorRecord {
+    /// The whole chain of the causes on one line
+    pub message: String,
+    /// The details of the parse error, if the error comes from a parser
+    pub parse: Option<ParseErrorRecord>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ParseErrorRecord {
+    pub line: Option<usize>,
+    /// 1-based, in chars
+    pub column: usize,
+    pub expected: String,
+    /// `None` if the expected thing is missing
+    pub found: Option<String>,
+    pub source_line: String,
+}
+
+impl From<&ParseError> for ParseErrorRecord {
+    fn from(error: &ParseError) -> Self {
+        ParseErrorRecord {
+            line: error.line,
+            column: error.column,
+            expected: error.expected.clone(),
+            found: match &error.kind {
+                ParseErrorKind::Unexpected { found } => Some(found.clone()),
+                ParseErrorKind::Missing => None,
+            },
+            source_line: error.source_line.clone(),
+        }
+    }
+}
+
+impl From<&anyhow::Error> for ErrorRecord {
+    fn from(error: &anyhow::Error) -> Self {
+        let message = error
+            .chain()
+            .map(|cause| match cause.downcast_ref::<ParseError>() {
+                // The usual message has the source line and the caret on the next lines
+                Some(parse_error) => parse_error.message(),
+                None => cause.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(": ");
+
+        let parse = error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<ParseError>())
+            .map(ParseErrorRecord::from);
+
+        ErrorRecord { message, parse }
+    }
+}
+
+pub fn records(run: &DayRun) -> Vec<Record> {
+    run.parts
+        .iter()
+        .map(|part| Record {
+            day: run.number,
+            part: part.number,
+            answer: part.answer.as_ref().ok().map(|a| a.to_string()),
+            elapsed_ms: Some(part.elapsed.as_secs_f64() * 1000.0),
+            error: part.answer.as_ref().err().map(ErrorRecord::from),
+        })
+        .collect()
+}
+
+/// The records of the parts that couldn't run because of `error`
+pub fn failed_records(day: u8, parts: &[usize], error: &anyhow::Error) -> Vec<Record> {
+    parts
+        .iter()
+        .map(|&part| Record {
+            day,
+            part,
+            answer: None,
+            elapsed_ms: None,
+            error: Some(ErrorRecord::from(error)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run::PartRun;
+    use anyhow::Context;
+    use aoc_common::Answer;
+    use std::time::Duration;
+
+    #[test]
+    fn records_the_answers_and_the_errors() {
+        let line = "acx +1";
+        let parse_error = ParseError::new(line, &line[..3], "acc, jmp or nop").at_line(2);
+        let run = DayRun {
+            number: 8,
+            parse_elapsed: None,
+            parts: vec![
+                PartRun {
+                    number: 1,
+                    answer: Ok(Answer::from("a,b")),
+                    elapsed: Duration::from_micros(1500),
+                },
+                PartRun {
+                    number: 2,
+                    answer: Err(parse_error.into()),
+                    elapsed: Duration::from_millis(2),
+                },
+            ],
+            peak_memory: 0,
+        };
+
+        let records = records(&run);
+        assert_eq!(
+            serde_json::to_string(&records[0]).unwrap(),
+            r#"{"day":8,"part":1,"answer":"a,b","elapsed_ms":1.5,"error":null}"#
+        );
+        assert_eq!(records[1].answer, None);
+        assert_eq!(
+            records[1].error,
+            Some(ErrorRecord {
+                message: "line 2, column 1: expected acc, jmp or nop, found \"acx\"".to_string(),
+                parse: Some(ParseErrorRecord {
+                    line: Some(2),
+                    column: 1,
+                    expected: "acc, jmp or nop".to_string(),
+                    found: Some("acx".to_string()),
+                    source_line: "acx +1".to_string(),
+                }),
+            })
+        );
+    }
+
+    #[test]
+    fn records_the_parts_that_did_not_run() {
+        let error = std::fs::read_to_string("/nowhere")
+            .context("Can't read /nowhere")
+            .unwrap_err();
+        let records = failed_records(3, &[1, 2], &error);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].part, 2);
+        assert_eq!(records[1].elapsed_ms, None);
+        let error = records[1].error.as_ref().unwrap();
+        assert!(error.message.starts_with("Can't read /nowhere: "));
+        assert_eq!(error.parse, None);
+    }
+}
@@ -0,0 +1,8 @@
+//! The days of the runner and the ways to run them, shared between the binary and the benchmarks
+
+pub mod cache;
+pub mod days;
+pub mod json;
+pub mod memory;
+pub mod run;
+pub mod timing;
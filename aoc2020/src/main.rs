@@ -0,0 +1,452 @@
+//! Runs any day and part of the solutions from one binary
+
+use anyhow::{bail, Context, Result};
+use aoc2020::cache::Cache;
+use aoc2020::days::{self, Day, DAYS};
+use aoc2020::json;
+use aoc2020::memory::PeakAlloc;
+use aoc2020::run::{run_day, run_days_parallel, DayRun, Input, Solver};
+use aoc2020::timing;
+use aoc_common::{input, print_answer, Params};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[clap(about = "Advent of Code 2020 solutions")]
+struct Cli {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one day, or every day in sequence if the day is omitted
+    Run(RunArgs),
+    /// Print a synthetic input of a day, and its known answers to stderr
+    Generate(GenerateArgs),
+    /// Solve many synthetic inputs of one day, or every day, and report PASS/FAIL for each of them
+    Stress(StressArgs),
+    /// Remove the cached answers
+    #[clap(subcommand)]
+    Cache(CacheCommand),
+}
+
+#[derive(Subcommand)]
+enum CacheCommand {
+    /// Remove every cached answer, or the answers of one day
+    Clear {
+        /// The day to remove the answers of, from 1 to 25
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+    },
+    /// Remove the answers of the solutions that have changed since
+    Prune,
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// The day to run, from 1 to 25
+    #[clap(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+
+    /// Run only this part
+    #[clap(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// The input file, `-` reads stdin [default: aocNN/input/input.txt]
+    #[clap(short, long, requires = "day")]
+    input: Option<PathBuf>,
+
+    /// Compare the answers with aocNN/input/answers.txt and report PASS/FAIL for each part
+    #[clap(long, conflicts_with = "input")]
+    check: bool,
+
+    /// Report the parse and part times and the peak memory of each day, and a table sorted by cost in the end
+    ///
+    /// The days run one by one then, so that they don't disturb each other's timings.
+    #[clap(long)]
+    time: bool,
+
+    /// The number of threads to run the days and their parts on [default: the number of CPUs]
+    #[clap(short, long, conflicts_with = "time", value_parser = clap::value_parser!(u16).range(1..))]
+    jobs: Option<u16>,
+
+    /// Print the bare answers, or one JSON array with the answer, the time and the error of every part
+    #[clap(long, value_enum, default_value = "text", conflicts_with_all = &["check", "time"])]
+    format: Format,
+
+    /// Read the input line by line, for the huge inputs of the days 1, 2, 5, 8, 9, 12, 14, 18 and 24
+    ///
+    /// The other days are skipped. Stdin can be read only once, so with `--input -` it needs `--part`, and the parts
+    /// that read the input twice need a file.
+    #[clap(long)]
+    stream: bool,
+
+    /// Print what the solvers do to stderr, like the instructions of day 8 or the rounds of day 22
+    ///
+    /// The AOC_TRACE variable narrows it down with a filter, e.g. `AOC_TRACE=aoc16=debug`, and works without it too.
+    /// A cached answer would skip the solver, so `--trace` never uses the cache.
+    #[clap(long)]
+    trace: bool,
+
+    /// Solve the inputs again instead of taking the answers of the earlier runs from target/aoc-cache
+    ///
+    /// The answers are cached for the same day, part, solution, dependencies, build profile, options and input
+    /// bytes. `--time` and `--trace` never use the cache, so that they always measure or trace the solutions.
+    #[clap(long)]
+    no_cache: bool,
+
+    /// The options of the day after `--`, e.g. `aoc2020 run 9 -- --preamble 5`
+    ///
+    /// The defaults are the values of the real puzzle. An unknown option lists the options of the day.
+    #[clap(last = true, requires = "day", conflicts_with = "check")]
+    params: Vec<String>,
+}
+
+#[derive(Args)]
+struct GenerateArgs {
+    /// The day to generate the input for, from 1 to 25
+    #[clap(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// The seed of the input, the same seed gives the same input
+    #[clap(short, long, default_value_t = 0)]
+    seed: u64,
+
+    /// The size of the input, the meaning depends on the day [default: the size of the real input]
+    #[clap(long)]
+    scale: Option<usize>,
+}
+
+#[derive(Args)]
+struct StressArgs {
+    /// The day to stress, from 1 to 25
+    #[clap(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+
+    /// The number of inputs to solve, with the seeds from 0
+    #[clap(long, default_value_t = 10)]
+    seeds: u64,
+
+    /// The size of the inputs, the meaning depends on the day [default: the size of the real input]
+    #[clap(long, requires = "day")]
+    scale: Option<usize>,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAlloc = PeakAlloc;
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let trace = matches!(&cli.command, Command::Run(args) if args.trace);
+    aoc_common::trace::init(trace)?;
+
+    match cli.command {
+        Command::Run(args) => run(&args),
+        Command::Generate(args) => generate(&args),
+        Command::Stress(args) => stress(&args),
+        Command::Cache(command) => cache(&command),
+    }
+}
+
+fn run(args: &RunArgs) -> Result<()> {
+    let days = match args.day {
+        // We may call .unwrap() here because clap checked the range of the day
+        Some(number) => std::slice::from_ref(days::find(number).unwrap()),
+        None => DAYS,
+    };
+    // Only one day gets the options, because clap requires the day for them
+    let params = match args.day {
+        Some(number) => Params::from_args(&args.params, days[0].params)
+            .with_context(|| format!("Bad options for day {}", number))?,
+        None => Params::default(),
+    };
+
+    let mut selected = vec![];
+    for day in days {
+        let parts = select_parts(day, args)?;
+
+        if !parts.is_empty() {
+            selected.push((day, parts));
+        }
+    }
+
+    let runs = run_days(&selected, &params, args)?;
+
+    if args.format == Format::Json {
+        return report_json(&selected, runs);
+    }
+
+    let mut timed_runs = vec![];
+    let mut checked = 0;
+    let mut failed = 0;
+
+    // The days are reported in order, no matter in which order they finished
+    for ((day, _), run) in selected.iter().zip(runs) {
+        let run = run?;
+
+        let run = match args.check {
+            true => {
+                checked += run.parts.len();
+                failed += report_check(day, &run)?;
+                run
+            }
+            false => {
+                if args.day.is_none() {
+                    println!("Day {:02}", day.number);
+                }
+                report_answers(day, run)?
+            }
+        };
+
+        if args.time {
+            println!("{}", timing::day_summary(&run));
+            timed_runs.push(run);
+        }
+    }
+
+    if args.time {
+        println!();
+        print!("{}", timing::table(&timed_runs));
+    }
+
+    if failed > 0 {
+        bail!("{} of {} parts failed the check", failed, checked);
+    }
+
+    Ok(())
+}
+
+/// Runs the days one by one with `--time`, or all of them at once on `--jobs` threads
+///
+/// The runs come back in the order of the days. A day that can't read its input gets the error instead.
+fn run_days(
+    selected: &[(&Day, Vec<(usize, Solver)>)],
+    params: &Params,
+    args: &RunArgs,
+) -> Result<Vec<Result<DayRun>>> {
+    let cache = Cache::new(Cache::default_dir());
+    let cache = match args.no_cache || args.time || args.trace {
+        true => None,
+        false => Some(&cache),
+    };
+
+    if args.time {
+        return Ok(selected
+            .iter()
+            .map(|(day, parts)| {
+                let input = read_input(day, args)?;
+                Ok(run_day(day, parts, &input, params, cache, true))
+            })
+            .collect());
+    }
+
+    run_days_parallel(
+        selected,
+        args.jobs,
+        |day| read_input(day, args),
+        params,
+        cache,
+    )
+}
+
+/// Prints the records of every part as one JSON array
+///
+/// The failed parts become records with an error, so that one broken day doesn't hide the others.
+fn report_json(selected: &[(&Day, Vec<(usize, Solver)>)], runs: Vec<Result<DayRun>>) -> Result<()> {
+    let mut records = vec![];
+
+    for ((day, parts), run) in selected.iter().zip(runs) {
+        match run {
+            Ok(run) => records.extend(json::records(&run)),
+            Err(e) => {
+                let numbers = parts.iter().map(|&(number, _)| number).collect::<Vec<_>>();
+                records.extend(json::failed_records(day.number, &numbers, &e));
+            }
+        }
+    }
+
+    println!("{}", serde_json::to_string_pretty(&records)?);
+    Ok(())
+}
+
+/// The parts to run with their 1-based numbers
+fn select_parts(day: &Day, args: &RunArgs) -> Result<Vec<(usize, Solver)>> {
+    let solvers = match args.stream {
+        true if day.stream.is_empty() && args.day.is_none() => vec![],
+        true if day.stream.is_empty() => bail!("Day {} can't stream its input", day.number),
+        true => day.stream.iter().map(|&p| Solver::Stream(p)).collect(),
+        false => day
+            .parts
+            .iter()
+            .map(|&p| Solver::Text(p))
+            .collect::<Vec<_>>(),
+    };
+    let numbered = solvers
+        .iter()
+        .copied()
+        .enumerate()
+        .map(|(ix, p)| (ix + 1, p));
+
+    let selected = match args.part {
+        None => numbered.collect::<Vec<_>>(),
+        Some(part) => match solvers.get(usize::from(part) - 1) {
+            Some(&found) => vec![(usize::from(part), found)],
+            // Running every day with --part 2 just skips the days without the second part
+            None if args.day.is_none() => vec![],
+            None => bail!("Day {} has no part {}", day.number, part),
+        },
+    };
+
+    let stdin = args
+        .input
+        .as_ref()
+        .is_some_and(|path| path.as_os_str() == "-");
+    if args.stream && stdin && selected.len() > 1 {
+        bail!("Only one part can stream stdin, choose it with --part");
+    }
+
+    Ok(selected)
+}
+
+fn read_input(day: &Day, args: &RunArgs) -> Result<Input> {
+    let path = match &args.input {
+        Some(path) if path.as_os_str() == "-" => match args.stream {
+            true => return Ok(Input::Stdin),
+            false => return Ok(Input::Text(input::read_stdin()?)),
+        },
+        Some(path) => path.clone(),
+        None => day.default_input_path(),
+    };
+
+    match args.stream {
+        true => Ok(Input::File(path)),
+        false => Ok(Input::Text(input::read_file(&path)?)),
+    }
+}
+
+/// Prints the answers and stops at the first failed part
+fn report_answers(day: &Day, run: DayRun) -> Result<DayRun> {
+    for part in run.parts.iter() {
+        match &part.answer {
+            Ok(answer) => print_answer(answer)?,
+            Err(_) => break,
+        }
+    }
+
+    run.into_result()
+        .with_context(|| format!("Day {} failed", day.number))
+}
+
+/// Prints PASS/FAIL for each part and returns the number of failures
+///
+/// A failing part doesn't stop the check, so that we see all the regressions at once.
+fn report_check(day: &Day, run: &DayRun) -> Result<usize> {
+    let expected_answers = day.expected_answers()?;
+    let mut failed = 0;
+
+    for part in run.parts.iter() {
+        let expected = expected_answers.get(part.number - 1).with_context(|| {
+            format!(
+                "No expected answer for day {} part {}",
+                day.number, part.number
+            )
+        })?;
+
+        let label = format!("Day {:02} part {}", day.number, part.number);
+        match &part.answer {
+            Ok(answer) if &answer.to_string() == expected => println!("{}: PASS", label),
+            Ok(answer) => {
+                failed += 1;
+                println!("{}: FAIL", label);
+                println!("  expected: {}", expected);
+                println!("  actual:   {}", answer);
+            }
+            Err(e) => {
+                failed += 1;
+                println!("{}: FAIL", label);
+                println!("  expected: {}", expected);
+                println!("  error:    {:#}", e);
+            }
+        }
+    }
+
+    Ok(failed)
+}
+
+fn generate(args: &GenerateArgs) -> Result<()> {
+    // We may call .unwrap() here because clap checked the range of the day
+    let day = days::find(args.day).unwrap();
+    let scale = args.scale.unwrap_or(day.puzzle_scale);
+    day.check_scale(scale)?;
+    let generated = (day.generate)(args.seed, scale);
+
+    print!("{}", generated.input);
+    if !generated.params.is_empty() {
+        eprintln!("Options: {}", generated.params);
+    }
+    for (ix, answer) in generated.answers.iter().enumerate() {
+        match answer {
+            Some(answer) => eprintln!("Part {}: {}", ix + 1, answer),
+            None => eprintln!("Part {}: unknown", ix + 1),
+        }
+    }
+
+    Ok(())
+}
+
+/// Solves the synthetic inputs and checks the known answers
+///
+/// A failing input doesn't stop the others, its seed is enough to generate it again.
+fn stress(args: &StressArgs) -> Result<()> {
+    let days = match args.day {
+        // We may call .unwrap() here because clap checked the range of the day
+        Some(number) => std::slice::from_ref(days::find(number).unwrap()),
+        None => DAYS,
+    };
+
+    let mut checked = 0;
+    let mut failed = 0;
+
+    for day in days {
+        let scale = args.scale.unwrap_or(day.puzzle_scale);
+        day.check_scale(scale)?;
+
+        for seed in 0..args.seeds {
+            let label = format!("Day {:02} seed {}", day.number, seed);
+            checked += 1;
+
+            match (day.generate)(seed, scale).verify(day.parts) {
+                Ok(()) => println!("{}: PASS", label),
+                Err(e) => {
+                    failed += 1;
+                    println!("{}: FAIL", label);
+                    println!("  {:#}", e);
+                }
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!("{} of {} inputs failed", failed, checked);
+    }
+
+    Ok(())
+}
+
+fn cache(command: &CacheCommand) -> Result<()> {
+    let cache = Cache::new(Cache::default_dir());
+    let removed = match command {
+        CacheCommand::Clear { day } => cache.clear(*day)?,
+        CacheCommand::Prune => cache.prune()?,
+    };
+
+    println!("Removed {} cached answers", removed);
+    Ok(())
+}
@@ -0,0 +1,252 @@
+//! Runs the parts of a day and keeps everything we report about them
+
+use crate::cache::{self, Cache, Key};
+use crate::days::Day;
+use crate::memory;
+use anyhow::Result;
+use aoc_common::stream::{Source, StreamPart};
+use aoc_common::{in_day, input, Answer, Params, Part};
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tracing::{info_span, warn};
+
+/// One part of a day, which solves the whole input at once or reads it line by line
+#[derive(Clone, Copy)]
+pub enum Solver {
+    Text(Part),
+    Stream(StreamPart),
+}
+
+impl Solver {
+    fn solve(self, input: &Input, params: &Params) -> Result<Answer> {
+        match (self, input) {
+            (Solver::Text(part), Input::Text(text)) => part(text, params),
+            (Solver::Text(part), Input::File(path)) => part(&input::read_file(path)?, params),
+            (Solver::Stream(part), Input::Text(text)) => part(Source::Text(text), params),
+            (Solver::Stream(part), Input::File(path)) => part(Source::File(path), params),
+            (Solver::Stream(part), Input::Stdin) => part(Source::Stdin, params),
+            // The runner only leaves stdin to the streaming parts
+            (Solver::Text(part), Input::Stdin) => part(&input::read_stdin()?, params),
+        }
+    }
+}
+
+/// The input of a day, read at once or left in its file or in stdin for the streaming parts
+pub enum Input {
+    Text(String),
+    File(PathBuf),
+    /// Read by the one streaming part that runs
+    Stdin,
+}
+
+pub struct PartRun {
+    /// 1-based
+    pub number: usize,
+    pub answer: Result<Answer>,
+    pub elapsed: Duration,
+}
+
+pub struct DayRun {
+    pub number: u8,
+    /// Only measured when the timings are asked for, because the parts parse the input again
+    pub parse_elapsed: Option<Duration>,
+    pub parts: Vec<PartRun>,
+    /// In bytes, on top of the heap usage before the day started, or zero if the days ran in parallel
+    pub peak_memory: usize,
+}
+
+impl DayRun {
+    /// The time of the parts, which already includes the parsing
+    pub fn total_elapsed(&self) -> Duration {
+        self.parts.iter().map(|p| p.elapsed).sum()
+    }
+
+    pub fn part(&self, number: usize) -> Option<&PartRun> {
+        self.parts.iter().find(|p| p.number == number)
+    }
+
+    /// Gives the first error of the parts, if any
+    pub fn into_result(self) -> Result<Self> {
+        match self.parts.iter().any(|p| p.answer.is_err()) {
+            false => Ok(self),
+            // We may .unwrap() here, because we have just found the error
+            true => Err(self.parts.into_iter().find_map(|p| p.answer.err()).unwrap()),
+        }
+    }
+}
+
+/// Runs the given parts of the day one by one, all of them even if some fail
+pub fn run_day(
+    day: &Day,
+    parts: &[(usize, Solver)],
+    input: &Input,
+    params: &Params,
+    cache: Option<&Cache>,
+    time_parse: bool,
+) -> DayRun {
+    let baseline = memory::reset_peak();
+    let cache = with_input_hash(cache, input);
+
+    // The parts report the parse errors themselves, so we only need the time here
+    // The streaming parts parse one line at a time, so there is nothing to time on its own
+    let parse_elapsed = match input {
+        Input::Text(text) if time_parse => Some(timed(|| (day.parse)(text)).1),
+        _ => None,
+    };
+
+    let parts = parts
+        .iter()
+        .map(|&(number, part)| run_part(day, number, part, input, params, cache))
+        .collect();
+
+    DayRun {
+        number: day.number,
+        parse_elapsed,
+        parts,
+        peak_memory: memory::peak().saturating_sub(baseline),
+    }
+}
+
+/// Runs the given parts of the day at once on the current thread pool
+///
+/// The heap is shared with the other days running in parallel, so the peak memory isn't measured.
+pub fn run_day_parallel(
+    day: &Day,
+    parts: &[(usize, Solver)],
+    input: &Input,
+    params: &Params,
+    cache: Option<&Cache>,
+) -> DayRun {
+    let cache = with_input_hash(cache, input);
+    DayRun {
+        number: day.number,
+        parse_elapsed: None,
+        // The parts come back in the order of `parts`
+        parts: parts
+            .par_iter()
+            .map(|&(number, part)| run_part(day, number, part, input, params, cache))
+            .collect(),
+        peak_memory: 0,
+    }
+}
+
+/// Runs the days and their parts at once on `jobs` threads, or on as many as there are CPUs
+///
+/// The runs come back in the order of `selected`. A day that can't read its input gets the error instead.
+pub fn run_days_parallel(
+    selected: &[(&Day, Vec<(usize, Solver)>)],
+    jobs: Option<u16>,
+    read_input: impl Fn(&Day) -> Result<Input> + Sync,
+    params: &Params,
+    cache: Option<&Cache>,
+) -> Result<Vec<Result<DayRun>>> {
+    let pool = ThreadPoolBuilder::new()
+        // Zero lets rayon choose the number of CPUs
+        .num_threads(jobs.map_or(0, usize::from))
+        .build()?;
+
+    Ok(pool.install(|| {
+        selected
+            .par_iter()
+            .map(|(day, parts)| {
+                let input = read_input(day)?;
+                Ok(run_day_parallel(day, parts, &input, params, cache))
+            })
+            .collect()
+    }))
+}
+
+/// The input is hashed once for all the parts, and an input that can't be read is left for the parts to report
+fn with_input_hash<'a>(cache: Option<&'a Cache>, input: &Input) -> Option<(&'a Cache, u64)> {
+    let cache = cache?;
+    cache::input_hash(input).ok().map(|hash| (cache, hash))
+}
+
+fn run_part(
+    day: &Day,
+    number: usize,
+    part: Solver,
+    input: &Input,
+    params: &Params,
+    cache: Option<(&Cache, u64)>,
+) -> PartRun {
+    let _span = info_span!("part", day = day.number, part = number).entered();
+    let cache = cache.map(|(cache, input_hash)| (cache, Key::new(day, number, params, input_hash)));
+
+    let (answer, elapsed) = timed(|| {
+        if let Some(answer) = cache.as_ref().and_then(|(cache, key)| cache.get(key)) {
+            return Ok(answer);
+        }
+        let answer = part.solve(input, params);
+        if let (Some((cache, key)), Ok(answer)) = (&cache, &answer) {
+            // The answer is still right, the next run just solves the input again
+            if let Err(e) = cache.put(key, answer) {
+                warn!("{:#}", e);
+            }
+        }
+        answer
+    });
+
+    PartRun {
+        number,
+        answer: answer.map_err(|e| in_day(e, day.number)),
+        elapsed,
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::DAYS;
+
+    const SELECTED: [u8; 7] = [1, 2, 5, 9, 13, 16, 21];
+
+    /// The day, the part and the answer of every part in the order of the runs, without the times
+    fn answers(jobs: u16) -> Result<Vec<(u8, usize, Answer)>> {
+        let selected = DAYS
+            .iter()
+            .filter(|day| SELECTED.contains(&day.number))
+            .map(|day| {
+                let parts = day.parts.iter().map(|&p| Solver::Text(p));
+                (day, parts.enumerate().map(|(ix, p)| (ix + 1, p)).collect())
+            })
+            .collect::<Vec<_>>();
+        let read_input = |day: &Day| Ok(Input::File(day.default_input_path()));
+
+        let mut answers = vec![];
+        for run in run_days_parallel(&selected, Some(jobs), read_input, &Params::default(), None)? {
+            let run = run?;
+            for part in run.parts {
+                answers.push((run.number, part.number, part.answer?));
+            }
+        }
+        Ok(answers)
+    }
+
+    #[test]
+    fn the_jobs_keep_the_order_of_the_days_and_the_parts() -> Result<()> {
+        let sequential = answers(1)?;
+        let order = sequential
+            .iter()
+            .map(|&(day, part, _)| (day, part))
+            .collect::<Vec<_>>();
+        let expected = SELECTED
+            .iter()
+            .flat_map(|&day| vec![(day, 1), (day, 2)])
+            .collect::<Vec<_>>();
+        assert_eq!(order, expected);
+
+        for &jobs in &[2, 4, 8] {
+            assert_eq!(answers(jobs)?, sequential, "{} jobs", jobs);
+        }
+        Ok(())
+    }
+}
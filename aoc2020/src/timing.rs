@@ -0,0 +1,131 @@
+//! Formats the timings and the peak memory of the days
+
+use crate::run::DayRun;
+use std::fmt::Write;
+use std::time::Duration;
+
+pub fn format_duration(duration: Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
+
+pub fn format_memory(bytes: usize) -> String {
+    const KIB: usize = 1 << 10;
+    const MIB: usize = 1 << 20;
+
+    match bytes {
+        b if b >= MIB => format!("{:.1} MiB", b as f64 / MIB as f64),
+        b if b >= KIB => format!("{:.1} KiB", b as f64 / KIB as f64),
+        b => format!("{} B", b),
+    }
+}
+
+fn format_part(run: &DayRun, number: usize) -> String {
+    run.part(number)
+        .map_or_else(|| "-".to_string(), |p| format_duration(p.elapsed))
+}
+
+fn format_parse(run: &DayRun) -> String {
+    run.parse_elapsed
+        .map_or_else(|| "-".to_string(), format_duration)
+}
+
+/// One line with the timings of the day
+pub fn day_summary(run: &DayRun) -> String {
+    format!(
+        "Parse {}, part 1 {}, part 2 {}, peak memory {}",
+        format_parse(run),
+        format_part(run, 1),
+        format_part(run, 2),
+        format_memory(run.peak_memory)
+    )
+}
+
+/// The table of the days, the most expensive first
+pub fn table(runs: &[DayRun]) -> String {
+    let mut runs = runs.iter().collect::<Vec<_>>();
+    runs.sort_by_key(|r| std::cmp::Reverse(r.total_elapsed()));
+
+    let mut table = format!(
+        "{:>3} {:>12} {:>12} {:>12} {:>12} {:>12}\n",
+        "Day", "Parse", "Part 1", "Part 2", "Total", "Peak memory"
+    );
+
+    for run in runs {
+        // We may .unwrap() here, because writing to a String never fails
+        writeln!(
+            table,
+            "{:>3} {:>12} {:>12} {:>12} {:>12} {:>12}",
+            run.number,
+            format_parse(run),
+            format_part(run, 1),
+            format_part(run, 2),
+            format_duration(run.total_elapsed()),
+            format_memory(run.peak_memory)
+        )
+        .unwrap();
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run::PartRun;
+    use aoc_common::Answer;
+
+    fn day_run(number: u8, part_ms: &[u64]) -> DayRun {
+        DayRun {
+            number,
+            parse_elapsed: Some(Duration::from_micros(500)),
+            parts: part_ms
+                .iter()
+                .enumerate()
+                .map(|(ix, &ms)| PartRun {
+                    number: ix + 1,
+                    answer: Ok(Answer::from(0_u8)),
+                    elapsed: Duration::from_millis(ms),
+                })
+                .collect(),
+            peak_memory: 3 << 20,
+        }
+    }
+
+    #[test]
+    fn formats_the_units() {
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.500 ms");
+        assert_eq!(format_memory(512), "512 B");
+        assert_eq!(format_memory(1536), "1.5 KiB");
+        assert_eq!(format_memory(3 << 20), "3.0 MiB");
+    }
+
+    #[test]
+    fn sorts_the_table_by_cost() {
+        let runs = [
+            day_run(1, &[1, 2]),
+            day_run(15, &[3, 900]),
+            day_run(25, &[7]),
+        ];
+        let table = table(&runs);
+        let days = table
+            .lines()
+            .skip(1)
+            .map(|l| l.split_whitespace().next().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(days, ["15", "25", "1"]);
+        // Day 25 has no second part
+        assert!(table
+            .lines()
+            .nth(2)
+            .unwrap()
+            .ends_with("7.000 ms            -     7.000 ms      3.0 MiB"));
+    }
+
+    #[test]
+    fn summarizes_a_day() {
+        assert_eq!(
+            day_summary(&day_run(25, &[7])),
+            "Parse 0.500 ms, part 1 7.000 ms, part 2 -, peak memory 3.0 MiB"
+        );
+    }
+}
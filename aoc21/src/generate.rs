@@ -0,0 +1,143 @@
+//! Food lists where each allergen can be narrowed down to one ingredient
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::{BTreeMap, HashSet};
+use std::ops::RangeInclusive;
+
+/// The number of foods in the real input
+pub const PUZZLE_SCALE: usize = 34;
+
+/// The numbers of foods
+pub const SCALES: RangeInclusive<usize> = 4..=usize::MAX;
+
+const ALLERGENS: [&str; 8] = [
+    "dairy",
+    "eggs",
+    "fish",
+    "nuts",
+    "peanuts",
+    "sesame",
+    "shellfish",
+    "soy",
+];
+
+/// Makes `scale` foods, at least 4, with an allergen for every 4 foods and at most 8 of them
+///
+/// The foods may contain the allergens they don't list, so we make new foods until the allergens can be matched one by
+/// one, like the day does it. The answers come from the ingredients we chose for the allergens.
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    let count = scale.max(*SCALES.start());
+    let allergens = &ALLERGENS[..(count / 4).min(ALLERGENS.len())];
+
+    let mut ingredients = HashSet::new();
+    while ingredients.len() < 200 {
+        let name = (0..rng.gen_range(4..=8))
+            .map(|_| char::from(rng.gen_range(b'a'..=b'z')))
+            .collect::<String>();
+        ingredients.insert(name);
+    }
+    let mut ingredients = ingredients.into_iter().collect::<Vec<_>>();
+    // The set has its own random order, which doesn't depend on the seed
+    ingredients.sort_unstable();
+    ingredients.shuffle(&mut rng);
+    let (dangerous, safe) = ingredients.split_at(allergens.len());
+
+    loop {
+        let mut foods = vec![];
+        let mut safe_count = 0;
+
+        for _ in 0..count {
+            let listed = allergens
+                .iter()
+                .enumerate()
+                .filter(|_| rng.gen_bool(0.3))
+                .map(|(ix, _)| ix)
+                .collect::<Vec<_>>();
+            // Every food lists at least one allergen, like the real ones
+            let listed = match listed.is_empty() {
+                true => vec![rng.gen_range(0..allergens.len())],
+                false => listed,
+            };
+
+            let amount = rng.gen_range(5..=20);
+            let mut food = safe
+                .choose_multiple(&mut rng, amount)
+                .cloned()
+                .collect::<Vec<_>>();
+            safe_count += food.len();
+            food.extend(
+                (0..allergens.len())
+                    .filter(|ix| listed.contains(ix) || rng.gen_bool(0.2))
+                    .map(|ix| dangerous[ix].clone()),
+            );
+            food.shuffle(&mut rng);
+
+            foods.push((food, listed));
+        }
+
+        if can_match(&foods, allergens.len()) {
+            let lines = foods
+                .iter()
+                .map(|(food, listed)| {
+                    let listed = listed.iter().map(|&ix| allergens[ix]).collect::<Vec<_>>();
+                    format!("{} (contains {})\n", food.join(" "), listed.join(", "))
+                })
+                .collect::<String>();
+
+            // The allergens are sorted already
+            let dangerous_list = dangerous.join(",");
+
+            return Generated {
+                input: lines,
+                answers: vec![
+                    Some(Answer::from(safe_count)),
+                    Some(Answer::from(dangerous_list)),
+                ],
+                params: Params::default(),
+            };
+        }
+    }
+}
+
+/// Whether the allergens can be matched one at a time to the only ingredient left in all the foods that list them
+fn can_match(foods: &[(Vec<String>, Vec<usize>)], allergen_count: usize) -> bool {
+    let mut matched = BTreeMap::new();
+
+    loop {
+        let matched_before = matched.len();
+
+        for allergen in 0..allergen_count {
+            if matched.contains_key(&allergen) {
+                continue;
+            }
+
+            let mut candidates: Option<HashSet<&String>> = None;
+            for (food, _) in foods.iter().filter(|(_, l)| l.contains(&allergen)) {
+                let food = food
+                    .iter()
+                    .filter(|&i| !matched.values().any(|&m| m == i))
+                    .collect::<HashSet<_>>();
+                candidates = Some(match candidates {
+                    None => food,
+                    Some(c) => c.intersection(&food).copied().collect(),
+                });
+            }
+
+            if let Some(candidates) = candidates.filter(|c| c.len() == 1) {
+                // We may .unwrap() here, because there is exactly one candidate
+                matched.insert(allergen, candidates.into_iter().next().unwrap());
+            }
+        }
+
+        if matched.len() == allergen_count {
+            return true;
+        }
+        if matched.len() == matched_before {
+            return false;
+        }
+    }
+}
@@ -0,0 +1,144 @@
+pub mod generate;
+
+use anyhow::{bail, Result};
+use aoc_common::parse::parse_lines;
+use aoc_common::ParseError;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use tracing::{debug, trace};
+
+pub fn part1(input: &str) -> Result<usize> {
+    let (foods, detected) = get_data(input)?;
+
+    Ok(foods
+        .iter()
+        .map(|f| {
+            f.ingredients
+                .iter()
+                .filter(|&i| !detected.contains_key(i))
+                .count()
+        })
+        .sum::<usize>())
+}
+
+pub fn part2(input: &str) -> Result<String> {
+    let (_, detected) = get_data(input)?;
+    let mut detected = detected.into_iter().collect::<Vec<_>>();
+    detected.sort_unstable_by_key(|e| e.1.to_string());
+
+    Ok(detected
+        .into_iter()
+        .map(|e| e.0)
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+pub fn parse(input: &str) -> Result<impl Sized> {
+    get_foods(input)
+}
+
+fn get_foods(input: &str) -> Result<Vec<Food>> {
+    Ok(parse_lines(input, Food::from_str)?)
+}
+
+fn get_data(input: &str) -> Result<(Vec<Food>, HashMap<String, String>)> {
+    let foods = get_foods(input)?;
+
+    let allergens: HashSet<_> = foods
+        .iter()
+        .flat_map(|v| v.allergens.iter().map(|s| s.as_str()))
+        .collect();
+
+    let mut detected = HashMap::new();
+
+    while detected.len() < allergens.len() {
+        let detected_before = detected.len();
+
+        for &allergen in allergens.iter() {
+            // The ingredients are the keys, and the allergens are the values
+            if detected.values().any(|a| a == allergen) {
+                continue;
+            }
+
+            let intersect = foods
+                .iter()
+                .filter(|f| f.allergens.contains(allergen))
+                .map(|f| {
+                    let mut current_ingredients = f.ingredients.clone();
+
+                    for ingredient in detected.keys() {
+                        current_ingredients.remove(ingredient);
+                    }
+
+                    current_ingredients
+                })
+                .reduce(|acc, f| acc.intersection(&f).cloned().collect());
+
+            if let Some(intersect) = intersect {
+                if intersect.len() == 1 {
+                    // We may call .unwrap() here because we checked the length above
+                    let ingredient = intersect.iter().next().cloned().unwrap();
+                    debug!(allergen, ingredient = ingredient.as_str(), "detected");
+                    detected.insert(ingredient, allergen.to_string());
+                } else {
+                    trace!(allergen, candidates = ?intersect, "not yet");
+                }
+            }
+        }
+
+        if detected.len() == detected_before {
+            bail!("Some allergens can't be matched to a single ingredient");
+        }
+    }
+
+    Ok((foods, detected))
+}
+
+#[derive(Debug)]
+struct Food {
+    ingredients: HashSet<String>,
+    allergens: HashSet<String>,
+}
+
+impl FromStr for Food {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut value_iter = value.split(" (contains ");
+        // We may .unwrap() here, because split always gives at least one item
+        let ingredients_str = value_iter.next().unwrap();
+        let allergens_str = value_iter
+            .next()
+            .ok_or_else(|| ParseError::after(value, ingredients_str, "\" (contains \""))?;
+
+        Ok(Food {
+            ingredients: ingredients_str
+                .split_ascii_whitespace()
+                .map(|s| s.to_string())
+                .collect(),
+            allergens: allergens_str
+                .split(", ")
+                .map(|s| s.trim_end_matches(')').to_string())
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(EXAMPLE)?, 5);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(EXAMPLE)?, "mxmxvkd,sqjhc,fvjkl");
+        Ok(())
+    }
+}
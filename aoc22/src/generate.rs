@@ -0,0 +1,101 @@
+//! Decks of any number of cards
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::seq::SliceRandom;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::RangeInclusive;
+
+/// The number of cards in the real input
+pub const PUZZLE_SCALE: usize = 50;
+
+/// The numbers of cards
+pub const SCALES: RangeInclusive<usize> = 2..=usize::MAX;
+
+type Decks = (VecDeque<u32>, VecDeque<u32>);
+
+/// Deals the cards from 1 to `scale`, at least 2, half to each player
+///
+/// The answers come from playing the games with the plain rules. The sub-games remember their winners instead of taking
+/// the shortcut of the day, so they check the shortcut too. The recursive games get very long above about 60 cards.
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    let mut cards = (1..=scale.max(*SCALES.start()) as u32).collect::<Vec<_>>();
+    cards.shuffle(&mut rng);
+    let (first, second) = cards.split_at(cards.len() / 2);
+
+    let decks = (
+        first.iter().copied().collect::<VecDeque<_>>(),
+        second.iter().copied().collect::<VecDeque<_>>(),
+    );
+
+    let deck_text = |deck: &[u32]| deck.iter().map(|c| format!("{}\n", c)).collect::<String>();
+
+    Generated {
+        input: format!(
+            "Player 1:\n{}\nPlayer 2:\n{}",
+            deck_text(first),
+            deck_text(second)
+        ),
+        answers: vec![
+            Some(Answer::from(score(play(decks.clone(), None)))),
+            Some(Answer::from(score(play(decks, Some(&mut HashMap::new()))))),
+        ],
+        params: Params::default(),
+    }
+}
+
+/// Plays the game to the end and gives the deck of the winner, `true` if it's the first player
+///
+/// The game is recursive if there is a memory for the winners of the sub-games.
+fn play(mut decks: Decks, mut winners: Option<&mut HashMap<Decks, bool>>) -> (bool, VecDeque<u32>) {
+    let mut seen = HashSet::new();
+
+    while !decks.0.is_empty() && !decks.1.is_empty() {
+        if !seen.insert(decks.clone()) {
+            return (true, decks.0);
+        }
+
+        // We may .unwrap() here, because both decks have cards
+        let first = decks.0.pop_front().unwrap();
+        let second = decks.1.pop_front().unwrap();
+
+        let first_wins = match winners.as_mut() {
+            Some(winners)
+                if decks.0.len() >= first as usize && decks.1.len() >= second as usize =>
+            {
+                let sub_decks = (
+                    decks.0.iter().take(first as usize).copied().collect(),
+                    decks.1.iter().take(second as usize).copied().collect(),
+                );
+                match winners.get(&sub_decks) {
+                    Some(&first_wins) => first_wins,
+                    None => {
+                        let first_wins = play(sub_decks.clone(), Some(winners)).0;
+                        winners.insert(sub_decks, first_wins);
+                        first_wins
+                    }
+                }
+            }
+            _ => first > second,
+        };
+
+        match first_wins {
+            true => decks.0.extend([first, second]),
+            false => decks.1.extend([second, first]),
+        }
+    }
+
+    match decks.0.is_empty() {
+        false => (true, decks.0),
+        true => (false, decks.1),
+    }
+}
+
+fn score((_, deck): (bool, VecDeque<u32>)) -> u64 {
+    deck.iter()
+        .rev()
+        .zip(1..)
+        .map(|(&card, position)| u64::from(card) * position)
+        .sum()
+}
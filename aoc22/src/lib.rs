@@ -0,0 +1,283 @@
+pub mod generate;
+
+use anyhow::{anyhow, Result};
+use aoc_common::parse::{parse_token, split_numbered_groups};
+use aoc_common::ParseError;
+use std::cmp::Ordering;
+use std::collections::{HashSet, VecDeque};
+use std::str::FromStr;
+use tracing::{debug, trace};
+
+pub fn part1(input: &str) -> Result<u64> {
+    process(input, false)
+}
+
+pub fn part2(input: &str) -> Result<u64> {
+    process(input, true)
+}
+
+pub fn parse(input: &str) -> Result<impl Sized> {
+    get_player_data(input)
+}
+
+fn process(input: &str, recursive: bool) -> Result<u64> {
+    let (first_player, second_player) = get_player_data(input)?;
+
+    let (winner, _) = combat(recursive, first_player, second_player);
+
+    winner
+        .score()
+        .ok_or_else(|| anyhow!("The score of the winner is too big"))
+}
+
+fn get_player_data(input: &str) -> Result<(Deck, Deck)> {
+    let mut group_iter = split_numbered_groups(input);
+
+    // An empty input has no groups at all
+    let missing = |expected| ParseError::in_block(input, &input[input.len()..], expected);
+
+    let first_group = group_iter
+        .next()
+        .ok_or_else(|| missing("the deck of player 1"))?;
+    let second_group = group_iter
+        .next()
+        .ok_or_else(|| missing("the deck of player 2"))?;
+
+    let deck = |(start, group): (usize, &str)| Deck::from_str(group).map_err(|e| e.at_line(start));
+    let first_player = deck(first_group)?;
+    let second_player = deck(second_group)?;
+
+    // We need to check that all card values are unique
+    let mut dealt = HashSet::new();
+    for (start, group) in [first_group, second_group] {
+        for (ix, line) in group.lines().enumerate().skip(1) {
+            // We may .unwrap() here, because the deck has parsed
+            let card: u32 = line.parse().unwrap();
+            if !dealt.insert(card) {
+                return Err(ParseError::new(line, line, "a card that isn't dealt yet")
+                    .at_line(start + ix)
+                    .into());
+            }
+        }
+    }
+
+    Ok((first_player, second_player))
+}
+
+#[derive(Debug)]
+enum Winner {
+    First,
+    Second,
+}
+
+fn combat(recursive: bool, mut first_player: Deck, mut second_player: Deck) -> (Deck, Winner) {
+    let mut history: HashSet<(Vec<u32>, Vec<u32>)> = HashSet::new();
+    let mut round = 0;
+
+    // A deck may be empty from the start, e.g. in a sub-game for a zero card
+    while first_player.len() > 0 && second_player.len() > 0 {
+        let first_player_vec = first_player.to_vec();
+        let second_player_vec = second_player.to_vec();
+        let player_vecs = (first_player_vec, second_player_vec);
+
+        if history.contains(&(player_vecs)) {
+            debug!(round, "the decks repeat, the first player wins the game");
+            return (first_player, Winner::First);
+        }
+        round += 1;
+
+        history.insert(player_vecs);
+
+        let (winner, first, second) = match (first_player.top(), second_player.top()) {
+            (Some(first), Some(second))
+                if recursive
+                    && first_player.len() >= first as usize
+                    && second_player.len() >= second as usize =>
+            {
+                let first_deck = first_player.clone_take(first as usize);
+                let second_deck = second_player.clone_take(second as usize);
+                // The highest card is higher than the number of cards left, so it can't start a sub-game
+                // and always wins its round. With it, the first player can only win, or repeat a round and win
+                let who_won = match first_deck.max() > second_deck.max() {
+                    true => Winner::First,
+                    false => combat(true, first_deck, second_deck).1,
+                };
+                (who_won, first, second)
+            }
+            (Some(first), Some(second)) => match first.cmp(&second) {
+                Ordering::Less => (Winner::Second, first, second),
+                // We checked that the value of all the cards is unique, so this code is definitely unreachable
+                Ordering::Equal => unreachable!(),
+                Ordering::Greater => (Winner::First, first, second),
+            },
+            // This part of the code is definitely unreachable, because we have checked the lengths above
+            _ => unreachable!(),
+        };
+
+        trace!(recursive, round, first, second, ?winner, "round");
+        match winner {
+            Winner::First => {
+                first_player.move_bottom(first);
+                first_player.move_bottom(second);
+            }
+            Winner::Second => {
+                second_player.move_bottom(second);
+                second_player.move_bottom(first);
+            }
+        }
+    }
+
+    debug!(
+        round,
+        first = first_player.len(),
+        second = second_player.len(),
+        "game over"
+    );
+    match first_player.len() > 0 {
+        true => (first_player, Winner::First),
+        false => (second_player, Winner::Second),
+    }
+}
+
+#[derive(Debug)]
+struct Deck {
+    deck: VecDeque<u32>,
+}
+
+impl FromStr for Deck {
+    type Err = ParseError;
+
+    /// The line numbers of the errors are relative to the deck
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let deck = value
+            .lines()
+            .enumerate()
+            .skip(1)
+            .map(|(ix, l)| parse_token(l, l, "a card").map_err(|e| e.at_line(ix + 1)))
+            .collect::<Result<_, _>>()?;
+        Ok(Deck { deck })
+    }
+}
+
+impl Deck {
+    fn top(&mut self) -> Option<u32> {
+        self.deck.pop_front()
+    }
+
+    fn move_bottom(&mut self, card: u32) {
+        self.deck.push_back(card)
+    }
+
+    fn len(&self) -> usize {
+        self.deck.len()
+    }
+
+    fn score(&self) -> Option<u64> {
+        self.deck
+            .iter()
+            .rev()
+            .enumerate()
+            .try_fold(0_u64, |score, (ix, &card)| {
+                u64::from(card)
+                    .checked_mul(ix as u64 + 1)
+                    .and_then(|points| score.checked_add(points))
+            })
+    }
+
+    fn max(&self) -> Option<u32> {
+        self.deck.iter().copied().max()
+    }
+
+    fn to_vec(&self) -> Vec<u32> {
+        self.deck.iter().cloned().collect()
+    }
+
+    fn clone_take(&self, count: usize) -> Deck {
+        Deck {
+            deck: self.deck.iter().cloned().take(count).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("../input/input.txt");
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+    const EXAMPLE_2: &str = include_str!("../input/example2.txt");
+
+    #[test]
+    fn same_answers_for_lf_and_crlf() -> Result<()> {
+        let lf = INPUT.replace("\r\n", "\n");
+        let crlf = lf.replace('\n', "\r\n");
+
+        assert_eq!(part1(&lf)?, part1(&crlf)?);
+        assert_eq!(part2(&lf)?, part2(&crlf)?);
+        Ok(())
+    }
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(EXAMPLE)?, 306);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(EXAMPLE)?, 291);
+        Ok(())
+    }
+
+    #[test]
+    fn points_at_the_card_dealt_twice() {
+        let error = part1("Player 1:\n3\n1\n\nPlayer 2:\n2\n1\n").err().unwrap();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (Some(7), 1));
+        assert_eq!(error.expected, "a card that isn't dealt yet");
+
+        let error = part1("Player 1:\n3\n1\n").err().unwrap();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!(error.expected, "the deck of player 2");
+    }
+
+    #[test]
+    fn part2_stops_the_infinite_game() {
+        assert!(part2(EXAMPLE_2).is_ok());
+    }
+
+    fn permutations(cards: &[u32]) -> Vec<Vec<u32>> {
+        if cards.is_empty() {
+            return vec![vec![]];
+        }
+        let mut all = vec![];
+        for (ix, &card) in cards.iter().enumerate() {
+            let mut rest = cards.to_vec();
+            rest.remove(ix);
+            for mut permutation in permutations(&rest) {
+                permutation.insert(0, card);
+                all.push(permutation);
+            }
+        }
+        all
+    }
+
+    /// The sub-games skip the play when the first player has the highest card, the whole games play it out
+    #[test]
+    fn the_highest_card_wins_the_recursive_game_for_the_first_player() {
+        for cards in permutations(&[1, 2, 3, 4, 5, 6]) {
+            for split in 1..cards.len() {
+                let (first, second) = cards.split_at(split);
+                if !first.contains(&6) {
+                    continue;
+                }
+
+                let deck = |cards: &[u32]| Deck {
+                    deck: cards.iter().copied().collect(),
+                };
+                let (_, winner) = combat(true, deck(first), deck(second));
+                assert!(matches!(winner, Winner::First), "{:?} {:?}", first, second);
+            }
+        }
+    }
+}
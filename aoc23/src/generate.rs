@@ -0,0 +1,49 @@
+//! Random orders of the nine cups
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::seq::SliceRandom;
+use std::ops::RangeInclusive;
+
+/// The input is always the nine cups, so the scale means nothing here
+pub const PUZZLE_SCALE: usize = 9;
+
+/// Any scale, it means nothing here
+pub const SCALES: RangeInclusive<usize> = 0..=usize::MAX;
+
+/// Shuffles the nine cups, whatever the scale is
+///
+/// The first answer comes from moving the cups around in a plain vector. The second one takes ten million moves over a
+/// million cups, which only a linked list like the day's own can do in time, so it is unknown.
+pub fn generate(seed: u64, _scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    let mut cups = (1..=9).collect::<Vec<u32>>();
+    cups.shuffle(&mut rng);
+    let input = cups.iter().map(|c| c.to_string()).collect::<String>();
+
+    for _ in 0..100 {
+        // The current cup is always the first one, so we rotate it to the end after the move
+        let picked = cups.drain(1..4).collect::<Vec<_>>();
+        let destination = (1..cups[0])
+            .rev()
+            .chain((cups[0] + 1..=9).rev())
+            .find(|c| !picked.contains(c));
+        // We may .unwrap() here, because only three of the other eight cups are picked up
+        let destination = destination.unwrap();
+        // We may .unwrap() here, because the destination cup isn't picked up
+        let at = cups.iter().position(|&c| c == destination).unwrap();
+        cups.splice(at + 1..at + 1, picked);
+        cups.rotate_left(1);
+    }
+
+    // We may .unwrap() here, because the cup 1 is always there
+    let one = cups.iter().position(|&c| c == 1).unwrap();
+    cups.rotate_left(one);
+    let labels = cups[1..].iter().map(|c| c.to_string()).collect::<String>();
+
+    Generated {
+        input: format!("{}\n", input),
+        answers: vec![Some(Answer::from(labels)), None],
+        params: Params::default(),
+    }
+}
@@ -0,0 +1,189 @@
+pub mod generate;
+
+use anyhow::Result;
+use aoc_common::parse::OrInvalid;
+use aoc_common::{Param, Params, ParseError};
+use std::collections::HashSet;
+
+const PART1_MOVES: Param = Param {
+    name: "part1-moves",
+    default: "100",
+    help: "The number of moves in part 1",
+};
+
+const PART2_MOVES: Param = Param {
+    name: "part2-moves",
+    default: "10000000",
+    help: "The number of moves in part 2",
+};
+
+const CUPS: Param = Param {
+    name: "cups",
+    default: "1000000",
+    help: "The number of cups in part 2, counting the ones of the input",
+};
+
+pub const PARAMS: &[Param] = &[PART1_MOVES, PART2_MOVES, CUPS];
+
+pub fn part1(input: &str) -> Result<String> {
+    part1_with(input, &Params::default())
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    part2_with(input, &Params::default())
+}
+
+pub fn part1_with(input: &str, params: &Params) -> Result<String> {
+    let cups = game(get_data(input)?, params.get(&PART1_MOVES)?);
+
+    Ok(cups
+        .into_iter()
+        .skip(1)
+        // We may call .unwrap() here because we have verified that the input data is numbers from 1 to 9
+        // We could have handled it in some clever way, but I don't see the point
+        .map(|d| std::char::from_digit(d as u32, 10).unwrap())
+        .collect::<String>())
+}
+
+pub fn part2_with(input: &str, params: &Params) -> Result<usize> {
+    let cups: usize = params.get(&CUPS)?;
+    let cups = game(
+        get_data(input)?.into_iter().chain(10..=cups).collect(),
+        params.get(&PART2_MOVES)?,
+    );
+
+    let mut cups_iter = cups.into_iter().skip(1);
+    Ok(cups_iter.next().or_invalid()? * cups_iter.next().or_invalid()?)
+}
+
+pub fn parse(input: &str) -> Result<impl Sized> {
+    get_data(input)
+}
+
+// We may safely use indexes and .unwrap()s in this function because we have checked the input data in get_data()
+fn game(mut cups: Vec<usize>, moves: usize) -> Vec<usize> {
+    // We use some hand-made vec-based linked list
+    // Where each value points to the next value
+    let mut cups_linked_list: Vec<usize> = vec![0; cups.len() + 1];
+
+    let mut previous = *cups.iter().last().unwrap();
+
+    for &cup in cups.iter() {
+        cups_linked_list[previous] = cup;
+        previous = cup;
+    }
+
+    let mut current = *cups.first().unwrap();
+    let max = *cups.iter().max().unwrap();
+
+    for _ in 0..moves {
+        let mut pick_up = [0_usize; 3];
+        pick_up[0] = cups_linked_list[current];
+        pick_up[1] = cups_linked_list[pick_up[0]];
+        pick_up[2] = cups_linked_list[pick_up[1]];
+
+        // Close the loop
+        cups_linked_list[current] = cups_linked_list[pick_up[2]];
+
+        let mut destination = current - 1;
+        loop {
+            if destination == 0 {
+                destination = max;
+            }
+
+            if !pick_up.contains(&destination) {
+                break;
+            }
+
+            destination -= 1;
+        }
+
+        // Reinsert
+        cups_linked_list[pick_up[2]] = cups_linked_list[destination];
+        cups_linked_list[destination] = pick_up[0];
+
+        // Move current
+        current = cups_linked_list[current];
+    }
+
+    // Rebuild back
+    // 1 should be the first element
+    let mut current = 1;
+    for cup in &mut cups {
+        *cup = current;
+        current = cups_linked_list[current];
+    }
+
+    cups
+}
+
+fn get_data(input: &str) -> Result<Vec<usize>> {
+    Ok(get_cups(input.lines().next().unwrap_or(input)).map_err(|e| e.at_line(1))?)
+}
+
+/// The cups from 1 to 9, each of them once
+fn get_cups(line: &str) -> Result<Vec<usize>, ParseError> {
+    let mut cups = vec![];
+    let mut seen = HashSet::new();
+    for (offset, c) in line.char_indices() {
+        // We may use the index here, because the offset is at a char boundary
+        let token = &line[offset..offset + c.len_utf8()];
+        let cup = match c.to_digit(10) {
+            Some(cup @ 1..=9) => cup as usize,
+            _ => return Err(ParseError::new(line, token, "a cup from 1 to 9")),
+        };
+        if !seen.insert(cup) {
+            return Err(ParseError::new(line, token, "a cup that isn't there yet"));
+        }
+        cups.push(cup);
+    }
+
+    match cups.len() {
+        9 => Ok(cups),
+        _ => Err(ParseError::after(line, line, "all the cups from 1 to 9")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "389125467";
+
+    #[test]
+    fn ten_moves_of_the_example() -> Result<()> {
+        let params = Params::default().with(&PART1_MOVES, 10);
+        assert_eq!(part1_with(EXAMPLE, &params)?, "92658374");
+        Ok(())
+    }
+
+    #[test]
+    fn points_at_the_bad_cups() {
+        let error = |input: &str| {
+            let error = part1(input).err().unwrap();
+            let error = error.downcast_ref::<ParseError>().unwrap().clone();
+            (error.line, error.column, error.expected)
+        };
+        let expected = |column, expected: &str| (Some(1), column, expected.to_string());
+
+        assert_eq!(error("3891254607"), expected(9, "a cup from 1 to 9"));
+        assert_eq!(
+            error("3891254673"),
+            expected(10, "a cup that isn't there yet")
+        );
+        assert_eq!(error("38912546\n"), expected(9, "all the cups from 1 to 9"));
+        assert_eq!(error(""), expected(1, "all the cups from 1 to 9"));
+    }
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(EXAMPLE)?, "67384529");
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(EXAMPLE)?, 149245887792);
+        Ok(())
+    }
+}
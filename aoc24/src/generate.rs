@@ -0,0 +1,95 @@
+//! Random walks over the hexagonal floor
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
+
+/// The number of tiles in the real input
+pub const PUZZLE_SCALE: usize = 486;
+
+/// The numbers of paths
+pub const SCALES: RangeInclusive<usize> = 0..=usize::MAX;
+
+type Cube = (i32, i32, i32);
+
+/// The directions with their steps in cube coordinates, which the day doesn't use
+const DIRECTIONS: [(&str, Cube); 6] = [
+    ("e", (1, -1, 0)),
+    ("se", (0, -1, 1)),
+    ("sw", (-1, 0, 1)),
+    ("w", (-1, 1, 0)),
+    ("nw", (0, 1, -1)),
+    ("ne", (1, 0, -1)),
+];
+
+/// Makes `scale` paths to the tiles to flip
+///
+/// A quarter of the paths go to a tile flipped before in another order, so some of the tiles are flipped back.
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    let mut paths: Vec<Vec<usize>> = Vec::with_capacity(scale);
+
+    for _ in 0..scale {
+        let path = match paths.choose(&mut rng) {
+            Some(earlier) if rng.gen_bool(0.25) => {
+                let mut path = earlier.clone();
+                path.shuffle(&mut rng);
+                path
+            }
+            _ => (0..rng.gen_range(1..=20))
+                .map(|_| rng.gen_range(0..DIRECTIONS.len()))
+                .collect(),
+        };
+        paths.push(path);
+    }
+
+    let mut blacks = HashSet::new();
+    for path in &paths {
+        let tile = path
+            .iter()
+            .fold((0, 0, 0), |tile, &d| step(tile, DIRECTIONS[d].1));
+        if !blacks.remove(&tile) {
+            blacks.insert(tile);
+        }
+    }
+    let first_blacks = blacks.len();
+
+    for _ in 0..100 {
+        let mut neighbors: HashMap<Cube, usize> = HashMap::new();
+        for &tile in &blacks {
+            for (_, offset) in DIRECTIONS.iter() {
+                *neighbors.entry(step(tile, *offset)).or_default() += 1;
+            }
+        }
+        blacks = neighbors
+            .into_iter()
+            .filter(|(tile, count)| *count == 2 || (*count == 1 && blacks.contains(tile)))
+            .map(|(tile, _)| tile)
+            .collect();
+    }
+
+    let input = paths
+        .iter()
+        .map(|path| {
+            let mut line = path.iter().map(|&d| DIRECTIONS[d].0).collect::<String>();
+            line.push('\n');
+            line
+        })
+        .collect();
+
+    Generated {
+        input,
+        answers: vec![
+            Some(Answer::from(first_blacks)),
+            Some(Answer::from(blacks.len())),
+        ],
+        params: Params::default(),
+    }
+}
+
+fn step((x, y, z): Cube, (dx, dy, dz): Cube) -> Cube {
+    (x + dx, y + dy, z + dz)
+}
@@ -0,0 +1,209 @@
+pub mod generate;
+pub mod stream;
+
+use anyhow::Result;
+use aoc_common::automaton::{Automaton, HexGrid, Moore, Rule};
+use aoc_common::parse::parse_lines;
+use aoc_common::{Param, Params, ParseError};
+use std::collections::HashSet;
+use std::ops::Add;
+use std::str::FromStr;
+
+const DAYS: Param = Param {
+    name: "days",
+    default: "100",
+    help: "The number of days the art exhibit lasts in part 2",
+};
+
+pub const PARAMS: &[Param] = &[DAYS];
+
+pub fn part1(input: &str) -> Result<usize> {
+    part1_with(input, &Params::default())
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    part2_with(input, &Params::default())
+}
+
+/// The parameters only matter for the second part, but the runner passes them to both
+pub fn part1_with(input: &str, _: &Params) -> Result<usize> {
+    process(input, 0)
+}
+
+pub fn part2_with(input: &str, params: &Params) -> Result<usize> {
+    process(input, params.get(&DAYS)?)
+}
+
+pub fn parse(input: &str) -> Result<impl Sized> {
+    get_tiles(input)
+}
+
+fn process(input: &str, days: usize) -> Result<usize> {
+    Ok(spread(get_blacks(input)?, days))
+}
+
+/// The number of the black tiles after the days of the exhibit
+fn spread(blacks: HashSet<AxialCoordinate>, days: usize) -> usize {
+    let blacks = blacks.into_iter().map(|a| (a.0, a.1));
+
+    // A black tile stays black with 1 or 2 black neighbors, a white one turns black with exactly 2
+    let mut floor = Automaton::new(HexGrid, Moore, Rule::new(&[2], &[1, 2]), blacks);
+    floor.steps(days);
+
+    floor.live_count()
+}
+
+fn get_blacks(input: &str) -> Result<HashSet<AxialCoordinate>> {
+    let tiles = get_tiles(input)?;
+
+    let mut blacks: HashSet<AxialCoordinate> = HashSet::new();
+
+    for tile in tiles {
+        flip(&mut blacks, &tile);
+    }
+
+    Ok(blacks)
+}
+
+fn flip(blacks: &mut HashSet<AxialCoordinate>, tile: &Tile) {
+    let axial = tile.axial_coordinate();
+
+    match blacks.contains(&axial) {
+        true => blacks.remove(&axial),
+        false => blacks.insert(axial),
+    };
+}
+
+fn get_tiles(input: &str) -> Result<Vec<Tile>> {
+    Ok(parse_lines(input, Tile::from_str)?)
+}
+
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy)]
+struct AxialCoordinate(i32, i32);
+
+impl Add for AxialCoordinate {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        AxialCoordinate(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+#[derive(Debug)]
+struct Tile {
+    directions: Vec<Direction>,
+}
+
+impl Tile {
+    // There are many hexagonal coordinate systems
+    // But i like `axial`
+    // https://www.redblobgames.com/grids/hexagons/
+    fn axial_coordinate(&self) -> AxialCoordinate {
+        self.directions
+            .iter()
+            .fold(AxialCoordinate(0, 0), |acc, cur| cur.axial() + acc)
+    }
+}
+
+impl FromStr for Tile {
+    type Err = ParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        const DIRECTIONS: &str = "e, se, sw, w, nw or ne";
+
+        let mut chars = value.char_indices();
+        let mut directions = vec![];
+
+        while let Some((ix, first)) = chars.next() {
+            let direction = match first {
+                's' | 'n' => {
+                    let (second_ix, second) = chars
+                        .next()
+                        .ok_or_else(|| ParseError::new(value, &value[value.len()..], "e or w"))?;
+                    match (first, second) {
+                        ('n', 'e') => Direction::NorthEast,
+                        ('n', 'w') => Direction::NorthWest,
+                        ('s', 'e') => Direction::SouthEast,
+                        ('s', 'w') => Direction::SouthWest,
+                        _ => {
+                            let token = &value[second_ix..second_ix + second.len_utf8()];
+                            return Err(ParseError::new(value, token, "e or w"));
+                        }
+                    }
+                }
+                'e' => Direction::East,
+                'w' => Direction::West,
+                _ => {
+                    let token = &value[ix..ix + first.len_utf8()];
+                    return Err(ParseError::new(value, token, DIRECTIONS));
+                }
+            };
+            directions.push(direction);
+        }
+
+        Ok(Tile { directions })
+    }
+}
+
+#[derive(Debug)]
+enum Direction {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl Direction {
+    fn axial(&self) -> AxialCoordinate {
+        match self {
+            Direction::East => AxialCoordinate(1, 0),
+            Direction::SouthEast => AxialCoordinate(0, 1),
+            Direction::SouthWest => AxialCoordinate(-1, 1),
+            Direction::West => AxialCoordinate(-1, 0),
+            Direction::NorthWest => AxialCoordinate(0, -1),
+            Direction::NorthEast => AxialCoordinate(1, -1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::stream::same_answers;
+
+    const EXAMPLE: &str = include_str!("../input/example.txt");
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(EXAMPLE)?, 10);
+        Ok(())
+    }
+
+    #[test]
+    fn part2_example() -> Result<()> {
+        assert_eq!(part2(EXAMPLE)?, 2208);
+        Ok(())
+    }
+
+    #[test]
+    fn ten_days_of_the_example() -> Result<()> {
+        let params = Params::default().with(&DAYS, 10);
+        assert_eq!(part2_with(EXAMPLE, &params)?, 37);
+        Ok(())
+    }
+
+    #[test]
+    fn streams_the_same_answers() -> Result<()> {
+        let parts = aoc_common::parts![with part1_with, part2_with];
+        let stream_parts = aoc_common::stream_parts![with stream::part1_with, stream::part2_with];
+        let params = Params::default();
+        same_answers(EXAMPLE, parts, stream_parts, &params)?;
+        for seed in 0..3 {
+            let input = generate::generate(seed, 20).input;
+            same_answers(&input, parts, stream_parts, &params)?;
+        }
+        Ok(())
+    }
+}
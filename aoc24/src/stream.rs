@@ -0,0 +1,36 @@
+//! The same parts, reading the tiles line by line
+//!
+//! Only the black tiles stay, not the paths to them.
+
+use crate::{flip, spread, Tile, DAYS};
+use anyhow::Result;
+use aoc_common::stream::Source;
+use aoc_common::Params;
+use std::collections::HashSet;
+use std::str::FromStr;
+
+pub fn part1(source: Source<'_>) -> Result<usize> {
+    part1_with(source, &Params::default())
+}
+
+pub fn part2(source: Source<'_>) -> Result<usize> {
+    part2_with(source, &Params::default())
+}
+
+pub fn part1_with(source: Source<'_>, _: &Params) -> Result<usize> {
+    process(source, 0)
+}
+
+pub fn part2_with(source: Source<'_>, params: &Params) -> Result<usize> {
+    process(source, params.get(&DAYS)?)
+}
+
+fn process(source: Source<'_>, days: usize) -> Result<usize> {
+    let mut blacks = HashSet::new();
+    for line in source.lines()? {
+        let (number, line) = line?;
+        let tile = Tile::from_str(&line).map_err(|e| e.at_line(number))?;
+        flip(&mut blacks, &tile);
+    }
+    Ok(spread(blacks, days))
+}
@@ -0,0 +1,52 @@
+//! Handshakes with chosen loop sizes
+
+use aoc_common::generate::{rng, Generated};
+use aoc_common::{Answer, Params};
+use rand::Rng;
+use std::ops::RangeInclusive;
+
+/// The loop sizes of the real input are about ten million
+pub const PUZZLE_SCALE: usize = 10_000_000;
+
+/// The biggest loop sizes, which are less than the divisor
+pub const SCALES: RangeInclusive<usize> = 1..=DIVISOR as usize - 2;
+
+const DIVISOR: u64 = 20201227;
+const SUBJECT_NUMBER: u64 = 7;
+
+/// Picks the loop sizes of the card and the door up to `scale`, which must be less than the divisor
+///
+/// The encryption key is the subject number to the power of both loop sizes, which we compute without looking for any
+/// of them.
+pub fn generate(seed: u64, scale: usize) -> Generated {
+    let mut rng = rng(seed);
+    let max_loop_size = scale.clamp(*SCALES.start(), *SCALES.end()) as u64;
+    let card_loop_size = rng.gen_range(1..=max_loop_size);
+    let door_loop_size = rng.gen_range(1..=max_loop_size);
+
+    let card_pub_key = mod_pow(SUBJECT_NUMBER, card_loop_size);
+    let door_pub_key = mod_pow(SUBJECT_NUMBER, door_loop_size);
+    // The powers of the subject number repeat every DIVISOR - 1 steps
+    let key = mod_pow(
+        SUBJECT_NUMBER,
+        card_loop_size * door_loop_size % (DIVISOR - 1),
+    );
+
+    Generated {
+        input: format!("{}\n{}\n", card_pub_key, door_pub_key),
+        answers: vec![Some(Answer::from(key))],
+        params: Params::default(),
+    }
+}
+
+fn mod_pow(mut base: u64, mut exp: u64) -> u64 {
+    let mut result = 1;
+    while exp > 0 {
+        if exp % 2 == 1 {
+            result = result * base % DIVISOR;
+        }
+        exp >>= 1;
+        base = base * base % DIVISOR;
+    }
+    result
+}
@@ -0,0 +1,108 @@
+pub mod generate;
+
+use anyhow::{anyhow, bail, Result};
+use aoc_common::number_theory::{discrete_log, pow_mod, MAX_LOG_MODULUS};
+use aoc_common::parse::{parse_lines, parse_token};
+use aoc_common::{Param, Params, ParseError};
+
+const DIVISOR: Param = Param {
+    name: "divisor",
+    default: "20201227",
+    help: "The modulus of the handshake, at most 2^42",
+};
+
+const SUBJECT_NUMBER: Param = Param {
+    name: "subject-number",
+    default: "7",
+    help: "The subject number that makes the public keys",
+};
+
+pub const PARAMS: &[Param] = &[DIVISOR, SUBJECT_NUMBER];
+
+pub fn part1(input: &str) -> Result<u64> {
+    part1_with(input, &Params::default())
+}
+
+pub fn part1_with(input: &str, params: &Params) -> Result<u64> {
+    let divisor: u64 = params.get(&DIVISOR)?;
+    let subject_number = params.get(&SUBJECT_NUMBER)?;
+    if divisor == 0 {
+        bail!("The divisor must be positive");
+    }
+    // The loop size is found with a table of the square root of the divisor
+    if divisor > MAX_LOG_MODULUS {
+        bail!("The divisor must be at most {}", MAX_LOG_MODULUS);
+    }
+
+    let (card_pub_key, door_pub_key) = get_data(input)?;
+
+    // The transformed values are always less than the divisor, so a bigger key is never reached
+    let loop_size = Some(card_pub_key)
+        .filter(|&key| key < divisor)
+        .and_then(|key| discrete_log(subject_number, key, divisor))
+        .ok_or_else(|| anyhow!("The card public key is not a power of the subject number"))?;
+
+    Ok(pow_mod(door_pub_key, loop_size, divisor))
+}
+
+pub fn parse(input: &str) -> Result<impl Sized> {
+    get_data(input)
+}
+
+fn get_data(input: &str) -> Result<(u64, u64)> {
+    let mut keys = parse_lines(input, |l| parse_token(l, l, "a public key"))?.into_iter();
+
+    // The input may end too early, so we point right after it
+    let missing = |expected| ParseError::in_block(input, &input[input.len()..], expected);
+    let card_pub_key = keys
+        .next()
+        .ok_or_else(|| missing("the public key of the card"))?;
+    let door_pub_key = keys
+        .next()
+        .ok_or_else(|| missing("the public key of the door"))?;
+
+    Ok((card_pub_key, door_pub_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "5764801\n17807724\n";
+
+    #[test]
+    fn part1_example() -> Result<()> {
+        assert_eq!(part1(EXAMPLE)?, 14897079);
+        Ok(())
+    }
+
+    #[test]
+    fn points_at_the_bad_keys() {
+        let error = part1("5764801\n17807x24\n").err().unwrap();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((error.line, error.column), (Some(2), 1));
+        assert_eq!(error.expected, "a public key");
+
+        let error = part1("5764801\n").err().unwrap();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!(error.expected, "the public key of the door");
+    }
+
+    #[test]
+    fn another_handshake() -> Result<()> {
+        // 3 to the power of 2 and 4 modulo 7
+        let params = Params::default().with(&SUBJECT_NUMBER, 3).with(&DIVISOR, 7);
+        assert_eq!(part1_with("2\n4\n", &params)?, 2);
+
+        let params = Params::default().with(&DIVISOR, 0);
+        assert!(part1_with(EXAMPLE, &params).is_err());
+
+        let params = Params::default().with(&DIVISOR, u64::MAX);
+        let error = part1_with(EXAMPLE, &params).err().unwrap().to_string();
+        assert_eq!(
+            error,
+            format!("The divisor must be at most {}", MAX_LOG_MODULUS)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,6 @@
+#![no_main]
+use aoc2020::days;
+use libfuzzer_sys::fuzz_target;
+
+// We may .unwrap() here, because the day exists
+fuzz_target!(|data: &[u8]| days::find(4).unwrap().fuzz(data));